@@ -0,0 +1,539 @@
+//! Module containing the clients sending requests to Discord
+
+use std::sync::Arc;
+use std::time::Duration;
+use reqwest::{Response, RequestBuilder, StatusCode};
+use reqwest::header::{HeaderMap, USER_AGENT};
+use lazy_static::lazy_static;
+use crate::{WebhookUrl, RateLimiter, RetryPolicy, Error};
+use crate::types::DEFAULT_API_URL;
+
+lazy_static! {
+    /// Client used by the methods of the request types themselves, like [crate::WebhookRequest::execute_url]
+    static ref SHARED_CLIENT: WebhookClient = WebhookClient::new();
+}
+
+/// A client sending requests to Discord, wrapping a [reqwest::Client].
+/// Executing, editing and deleting messages and managing the webhook can be done with any client,
+/// the methods of the request types themselves, like [crate::WebhookRequest::execute_url], use [Self::shared].
+///
+/// Cloning a client is cheap, and the clone shares the connection pool and rate limits of the original
+#[derive(Clone, Debug)]
+pub struct WebhookClient {
+    /// the HTTP client requests are send with
+    http:       reqwest::Client,
+    /// how requests are send
+    config:     ClientConfig
+}
+
+/// How a client sends requests
+#[derive(Clone, Debug, Default)]
+struct ClientConfig {
+    /// the URL of the API, or `None` to use `https://discord.com/api`
+    base_url:       Option<reqwest::Url>,
+    /// the version of the API, or `None` to use the unversioned API
+    api_version:    Option<u8>,
+    /// the timeout of every request
+    timeout:        Option<Duration>,
+    /// the `User-Agent` header of every request
+    user_agent:     Option<String>,
+    /// the retry policy, or `None` to use [RetryPolicy::shared]
+    retry_policy:   Option<RetryPolicy>,
+    /// the rate limiter, or `None` to use [RateLimiter::shared]
+    rate_limiter:   Option<Arc<RateLimiter>>
+}
+
+impl ClientConfig {
+    /// The URL of the API requests are send to, including the API version
+    fn api_url(&self) -> String {
+        let base_url = self.base_url.as_ref()
+            .map(|url| url.as_str().trim_end_matches('/'))
+            .unwrap_or(DEFAULT_API_URL);
+
+        match self.api_version {
+            Some(version) => format!("{}/v{}", base_url, version),
+            None => base_url.to_string()
+        }
+    }
+
+    /// The retry policy of the client
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.clone().unwrap_or_else(RetryPolicy::shared)
+    }
+
+    /// The rate limiter of the client
+    fn rate_limiter(&self) -> &RateLimiter {
+        self.rate_limiter.as_deref().unwrap_or_else(|| RateLimiter::shared())
+    }
+}
+
+impl WebhookClient {
+    /// Create a client with a default [reqwest::Client], the shared retry policy and the shared rate limiter
+    pub fn new() -> Self {
+        Self::from(reqwest::Client::new())
+    }
+
+    /// The client used by the methods of the request types themselves
+    pub fn shared() -> &'static WebhookClient {
+        &SHARED_CLIENT
+    }
+
+    /// The HTTP client requests are send with
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// The URL of the API requests are send to, e.g. `https://discord.com/api/v10`
+    pub fn api_url(&self) -> String {
+        self.config.api_url()
+    }
+
+    /// Send the request build by `build` to the webhook at `url`, respecting its rate limits, see [RateLimiter],
+    /// and retrying transient failures, see [RetryPolicy]. Whether the request is `idempotent` decides if ambiguous failures are retried.
+    /// The request is build again for every attempt, as a multipart body cannot be reused
+    ///
+    /// # Errors
+    /// - When the request could not be build
+    /// - When a rate limit resets after more than [RateLimiter::max_wait]
+    /// - When the request fails, after the last retry
+    /// - When Discord responds with an unsuccessful status code, after the last retry
+    pub(crate) async fn send_request<F: Fn(&reqwest::Client) -> Result<RequestBuilder, Error>>(&self, url: &WebhookUrl, idempotent: bool, build: F) -> Result<Response, Error> {
+        let limiter = self.config.rate_limiter();
+        let policy = self.config.retry_policy();
+        let mut rate_limit_retries = 0;
+        let mut attempt = 1;
+
+        loop {
+            while let Some(delay) = limiter.acquire(url.id()) {
+                limiter.check_wait(delay)?;
+                tokio::time::sleep(delay).await;
+            }
+
+            let mut request = build(&self.http)?;
+            if let Some(timeout) = self.config.timeout {
+                request = request.timeout(timeout);
+            }
+
+            if let Some(user_agent) = &self.config.user_agent {
+                request = request.header(USER_AGENT, user_agent);
+            }
+
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => match policy.retry_error(&e, attempt, idempotent) {
+                    Some(delay) => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    },
+                    None => return Err(e.into())
+                }
+            };
+
+            let status = response.status().as_u16();
+            limiter.update(url.id(), status, response.headers());
+
+            if status == StatusCode::TOO_MANY_REQUESTS && rate_limit_retries < limiter.max_retries() {
+                rate_limit_retries += 1;
+                continue;
+            }
+
+            if let Some(delay) = policy.retry_status(status, attempt, idempotent) {
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            return check_response(response).await;
+        }
+    }
+}
+
+impl Default for WebhookClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wrap an existing client, e.g. one configured with a proxy or custom root certificates, or the one used by the rest of the application
+impl From<reqwest::Client> for WebhookClient {
+    fn from(http: reqwest::Client) -> Self {
+        Self { http, config: ClientConfig::default() }
+    }
+}
+
+/// Builder for WebhookClient, and for BlockingWebhookClient if the `blocking` feature is enabled
+#[derive(Default)]
+pub struct WebhookClientBuilder {
+    /// the HTTP client to wrap
+    http:           Option<reqwest::Client>,
+    /// the blocking HTTP client to wrap
+    #[cfg(feature = "blocking")]
+    blocking_http:  Option<reqwest::blocking::Client>,
+    /// how requests are send
+    config:         ClientConfig
+}
+
+impl WebhookClientBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the HTTP client to send requests with. Defaults to a new client
+    pub fn set_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// the blocking HTTP client to send requests with. Defaults to a new client
+    #[cfg(feature = "blocking")]
+    pub fn set_blocking_http_client(mut self, http: reqwest::blocking::Client) -> Self {
+        self.blocking_http = Some(http);
+        self
+    }
+
+    /// the URL of the API requests are send to, e.g. a local mock server or an egress proxy. Defaults to `https://discord.com/api`.
+    /// Endpoints are appended to the URL, e.g. `{base_url}/webhooks/{id}/{token}`, or `{base_url}/v{version}/webhooks/...` with an API version
+    pub fn set_base_url(mut self, base_url: reqwest::Url) -> Self {
+        self.config.base_url = Some(base_url);
+        self
+    }
+
+    /// the version of the API to use, e.g. `10` for `/api/v10`. Defaults to the unversioned API
+    pub fn set_api_version(mut self, version: u8) -> Self {
+        self.config.api_version = Some(version);
+        self
+    }
+
+    /// the timeout of every request, from connecting until the response body has been read
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// the `User-Agent` header of every request
+    pub fn set_user_agent<S: AsRef<str>>(mut self, user_agent: S) -> Self {
+        self.config.user_agent = Some(user_agent.as_ref().to_string());
+        self
+    }
+
+    /// the retry policy of the client. Defaults to [RetryPolicy::shared]
+    pub fn set_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.config.retry_policy = Some(policy);
+        self
+    }
+
+    /// the rate limiter of the client. Defaults to [RateLimiter::shared], which is recommended,
+    /// as Discord's rate limits apply to a webhook no matter which client is used
+    pub fn set_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Build the WebhookClient
+    pub fn build(self) -> WebhookClient {
+        WebhookClient {
+            http:   self.http.unwrap_or_default(),
+            config: self.config
+        }
+    }
+
+    /// Build the BlockingWebhookClient
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> BlockingWebhookClient {
+        BlockingWebhookClient {
+            http:   self.blocking_http.unwrap_or_default(),
+            config: self.config
+        }
+    }
+}
+
+/// The value of the `Retry-After` header in seconds, if present
+fn retry_after(headers: &HeaderMap) -> Option<f64> {
+    headers.get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Turn a response with an unsuccessful status code into an [Error]
+///
+/// # Errors
+/// - When the status code of the response is unsuccessful
+/// - When the body of an unsuccessful response could not be read
+async fn check_response(response: Response) -> Result<Response, Error> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = retry_after(response.headers());
+    let body = response.text().await?;
+    Err(Error::from_response(status.as_u16(), retry_after, body))
+}
+
+#[cfg(feature = "blocking")]
+pub use self::blocking::BlockingWebhookClient;
+
+/// The blocking client
+#[cfg(feature = "blocking")]
+mod blocking {
+    use reqwest::StatusCode;
+    use reqwest::header::USER_AGENT;
+    use reqwest::blocking::{Response, RequestBuilder};
+    use lazy_static::lazy_static;
+    use crate::{WebhookUrl, Error};
+    use super::{ClientConfig, retry_after};
+
+    lazy_static! {
+        /// Client used by the blocking methods of the request types themselves, like [crate::WebhookRequest::execute_url_sync]
+        static ref SHARED_BLOCKING_CLIENT: BlockingWebhookClient = BlockingWebhookClient::new();
+    }
+
+    /// A client sending blocking requests to Discord, wrapping a [reqwest::blocking::Client], see [crate::WebhookClient]
+    #[derive(Clone, Debug)]
+    pub struct BlockingWebhookClient {
+        /// the HTTP client requests are send with
+        pub(super) http:    reqwest::blocking::Client,
+        /// how requests are send
+        pub(super) config:  ClientConfig
+    }
+
+    impl BlockingWebhookClient {
+        /// Create a client with a default [reqwest::blocking::Client], the shared retry policy and the shared rate limiter
+        pub fn new() -> Self {
+            Self::from(reqwest::blocking::Client::new())
+        }
+
+        /// The client used by the blocking methods of the request types themselves
+        pub fn shared() -> &'static BlockingWebhookClient {
+            &SHARED_BLOCKING_CLIENT
+        }
+
+        /// The HTTP client requests are send with
+        pub fn http_client(&self) -> &reqwest::blocking::Client {
+            &self.http
+        }
+
+        /// The URL of the API requests are send to, e.g. `https://discord.com/api/v10`
+        pub fn api_url(&self) -> String {
+            self.config.api_url()
+        }
+
+        /// Send the request build by `build` to the webhook at `url`, see [crate::WebhookClient::send_request]
+        ///
+        /// # Errors
+        /// - When the request could not be build
+        /// - When a rate limit resets after more than [crate::RateLimiter::max_wait]
+        /// - When the request fails, after the last retry
+        /// - When Discord responds with an unsuccessful status code, after the last retry
+        pub(crate) fn send_request<F: Fn(&reqwest::blocking::Client) -> Result<RequestBuilder, Error>>(&self, url: &WebhookUrl, idempotent: bool, build: F) -> Result<Response, Error> {
+            let limiter = self.config.rate_limiter();
+            let policy = self.config.retry_policy();
+            let mut rate_limit_retries = 0;
+            let mut attempt = 1;
+
+            loop {
+                while let Some(delay) = limiter.acquire(url.id()) {
+                    limiter.check_wait(delay)?;
+                    std::thread::sleep(delay);
+                }
+
+                let mut request = build(&self.http)?;
+                if let Some(timeout) = self.config.timeout {
+                    request = request.timeout(timeout);
+                }
+
+                if let Some(user_agent) = &self.config.user_agent {
+                    request = request.header(USER_AGENT, user_agent);
+                }
+
+                let response = match request.send() {
+                    Ok(response) => response,
+                    Err(e) => match policy.retry_error(&e, attempt, idempotent) {
+                        Some(delay) => {
+                            std::thread::sleep(delay);
+                            attempt += 1;
+                            continue;
+                        },
+                        None => return Err(e.into())
+                    }
+                };
+
+                let status = response.status().as_u16();
+                limiter.update(url.id(), status, response.headers());
+
+                if status == StatusCode::TOO_MANY_REQUESTS && rate_limit_retries < limiter.max_retries() {
+                    rate_limit_retries += 1;
+                    continue;
+                }
+
+                if let Some(delay) = policy.retry_status(status, attempt, idempotent) {
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                    continue;
+                }
+
+                return check_response(response);
+            }
+        }
+    }
+
+    impl Default for BlockingWebhookClient {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Wrap an existing blocking client, see [crate::WebhookClient]
+    impl From<reqwest::blocking::Client> for BlockingWebhookClient {
+        fn from(http: reqwest::blocking::Client) -> Self {
+            Self { http, config: ClientConfig::default() }
+        }
+    }
+
+    /// Turn a response with an unsuccessful status code into an [Error], see [super::check_response]
+    ///
+    /// # Errors
+    /// - When the status code of the response is unsuccessful
+    /// - When the body of an unsuccessful response could not be read
+    fn check_response(response: Response) -> Result<Response, Error> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry_after = retry_after(response.headers());
+        let body = response.text()?;
+        Err(Error::from_response(status.as_u16(), retry_after, body))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+    use std::sync::Arc;
+    use crate::*;
+    use crate::stub::{stub_client, STUB_MESSAGE, STUB_UNAVAILABLE, STUB_SERVER_ERROR};
+
+    #[test]
+    fn webhook_client() {
+        let client = WebhookClientBuilder::new()
+            .set_blocking_http_client(reqwest::blocking::Client::new())
+            .set_timeout(Duration::from_secs(5))
+            .set_user_agent("dwbhk-rs")
+            .set_retry_policy(RetryPolicy::none())
+            .set_rate_limiter(Arc::new(RateLimiter::new(0)))
+            .build_blocking();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello world!").build())
+            .build();
+
+        assert!(matches!(client.execute(&req, "https://example.com/api/webhooks/1/token"), Err(Error::Url(_))));
+        assert!(matches!(client.get_webhook("https://discord.com/api/webhooks/abc/token"), Err(Error::Url(_))));
+
+        let client = WebhookClient::from(reqwest::Client::new());
+        assert!(format!("{:?}", client).contains("WebhookClient"));
+    }
+
+    #[test]
+    fn client_base_url() {
+        let (builder, server) = stub_client(vec![STUB_UNAVAILABLE, STUB_MESSAGE]);
+        let client = builder
+            .set_api_version(10)
+            .set_user_agent("dwbhk-rs")
+            .build_blocking();
+
+        assert!(client.api_url().starts_with("http://127.0.0.1:"));
+        assert!(client.api_url().ends_with("/api/v10"));
+
+        let message = client.get_message(WebhookUrl::new("1", "token").unwrap(), "2").unwrap();
+        assert_eq!(message.id, "2");
+
+        // Getting a message is idempotent, so the server error is retried
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert!(request.starts_with("get /api/v10/webhooks/1/token/messages/2 http/1.1"), "{}", request);
+            assert!(request.contains("user-agent: dwbhk-rs"));
+        }
+    }
+
+    #[test]
+    fn client_does_not_retry_execute() {
+        let (builder, server) = stub_client(vec![STUB_SERVER_ERROR]);
+        let client = builder.build_blocking();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello world!").build())
+            .build();
+
+        // The message may have been posted, so retrying could post it twice
+        let err = client.execute_wait(&req, WebhookUrl::new("1", "token").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::Status(StatusError { status: 500, .. })));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("post /api/webhooks/1/token?wait=true http/1.1"));
+    }
+
+    #[test]
+    fn client_retries_unavailable_execute() {
+        let (builder, server) = stub_client(vec![STUB_UNAVAILABLE, STUB_MESSAGE]);
+        let client = builder.build_blocking();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello world!").build())
+            .build();
+
+        // Discord did not process the message, so it is not posted twice
+        let message = client.execute_wait(&req, WebhookUrl::new("1", "token").unwrap()).unwrap();
+        assert_eq!(message.id, "2");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn client_does_not_wait_for_long_rate_limits() {
+        let rate_limited = "HTTP/1.1 429 Too Many Requests\r\nConnection: close\r\nRetry-After: 100000\r\nContent-Length: 0\r\n\r\n";
+        let (builder, server) = stub_client(vec![rate_limited]);
+        let client = builder
+            .set_rate_limiter(Arc::new(RateLimiter::new(1)))
+            .build_blocking();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello world!").build())
+            .build();
+
+        let start = std::time::Instant::now();
+        match client.execute_wait(&req, WebhookUrl::new("1", "token").unwrap()) {
+            Err(Error::RateLimited(e)) => assert!(e.retry_after > DEFAULT_MAX_RATE_LIMIT_WAIT),
+            result => panic!("Expected a rate limit error, got {:?}", result)
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn client_retries_rate_limited_execute() {
+        let rate_limited = "HTTP/1.1 429 Too Many Requests\r\nConnection: close\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n";
+        let (builder, server) = stub_client(vec![rate_limited, STUB_MESSAGE]);
+        let client = builder
+            .set_retry_policy(RetryPolicy::none())
+            .set_rate_limiter(Arc::new(RateLimiter::new(1)))
+            .build_blocking();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello world!").build())
+            .build();
+
+        // A rejected request has not been processed, so it is safe to retry
+        let message = client.execute_wait(&req, WebhookUrl::new("1", "token").unwrap()).unwrap();
+        assert_eq!(message.id, "2");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+}
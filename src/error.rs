@@ -0,0 +1,345 @@
+//! Errors which can occur while building or executing a webhook
+
+use std::fmt;
+use std::time::Duration;
+use serde::Deserialize;
+use crate::types::redact_url;
+use crate::ratelimit::seconds;
+
+/// An error occurring while executing a webhook
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be send, or no (complete) response was received
+    Transport(reqwest::Error),
+    /// Discord responded with an unsuccessful status code, without an error body the crate understands
+    Status(StatusError),
+    /// Discord rejected the request with a JSON error body
+    Discord(DiscordError),
+    /// Discord rejected the request because a rate limit was hit
+    RateLimited(RateLimitError),
+    /// The request was not send, because it does not satisfy Discord's requirements
+    Validation(ValidationError),
+    /// The request was not send, because the webhook URL is invalid
+    Url(UrlError)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(e) => write!(f, "Failed to execute request: {}", e),
+            Self::Status(e) => e.fmt(f),
+            Self::Discord(e) => e.fmt(f),
+            Self::RateLimited(e) => e.fmt(f),
+            Self::Validation(e) => e.fmt(f),
+            Self::Url(e) => e.fmt(f)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(e) => Some(e),
+            Self::Status(e) => Some(e),
+            Self::Discord(e) => Some(e),
+            Self::RateLimited(e) => Some(e),
+            Self::Validation(e) => Some(e),
+            Self::Url(e) => Some(e)
+        }
+    }
+}
+
+/// The webhook token in the URL of the error is redacted
+impl From<reqwest::Error> for Error {
+    fn from(mut e: reqwest::Error) -> Self {
+        if let Some(url) = e.url_mut() {
+            redact_url(url);
+        }
+
+        Self::Transport(e)
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(e: ValidationError) -> Self {
+        Self::Validation(e)
+    }
+}
+
+impl From<UrlError> for Error {
+    fn from(e: UrlError) -> Self {
+        Self::Url(e)
+    }
+}
+
+impl Error {
+    /// Create an error from an unsuccessful response's status code, `Retry-After` header and body
+    pub(crate) fn from_response(status: u16, retry_after_header: Option<f64>, body: String) -> Self {
+        if status == 429 {
+            let rate_limit = serde_json::from_str::<RateLimitBody>(&body).ok();
+            let retry_after = rate_limit.as_ref()
+                .map(|rate_limit| rate_limit.retry_after)
+                .or(retry_after_header)
+                .unwrap_or_default();
+
+            return Self::RateLimited(RateLimitError {
+                retry_after:    seconds(retry_after),
+                global:         rate_limit.as_ref().map(|rate_limit| rate_limit.global).unwrap_or_default(),
+                message:        rate_limit.map(|rate_limit| rate_limit.message).unwrap_or_default()
+            });
+        }
+
+        match serde_json::from_str::<DiscordErrorBody>(&body) {
+            Ok(error_body) => {
+                let mut errors = Vec::new();
+                if let Some(field_errors) = &error_body.errors {
+                    collect_field_errors(field_errors, String::new(), &mut errors);
+                }
+
+                Self::Discord(DiscordError {
+                    status,
+                    code:       error_body.code,
+                    message:    error_body.message,
+                    errors
+                })
+            },
+            Err(_) => Self::Status(StatusError { status, body })
+        }
+    }
+}
+
+/// Discord responded with an unsuccessful status code
+#[derive(Debug, Clone)]
+pub struct StatusError {
+    /// the HTTP status code
+    pub status:     u16,
+    /// the response body
+    pub body:       String
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Discord responded with status {}", self.status)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// An error as reported by Discord
+#[derive(Debug, Clone)]
+pub struct DiscordError {
+    /// the HTTP status code
+    pub status:     u16,
+    /// Discord's JSON error code
+    pub code:       u64,
+    /// human readable description of the error
+    pub message:    String,
+    /// errors for individual fields of the payload
+    pub errors:     Vec<FieldError>
+}
+
+impl fmt::Display for DiscordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Discord responded with status {}: {} (code {})", self.status, self.message, self.code)?;
+        for error in &self.errors {
+            write!(f, "; {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for DiscordError {}
+
+/// An error Discord reported for an individual field of the payload
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    /// path to the field, e.g. `embeds.0.title`
+    pub path:       String,
+    /// Discord's error code, e.g. `BASE_TYPE_MAX_LENGTH`
+    pub code:       String,
+    /// human readable description of the error
+    pub message:    String
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({})", self.path, self.message, self.code)
+    }
+}
+
+/// Discord rejected the request because a rate limit was hit
+#[derive(Debug, Clone)]
+pub struct RateLimitError {
+    /// how long to wait before the request may be retried
+    pub retry_after:    Duration,
+    /// whether the global rate limit was hit, rather than the webhook's own limit
+    pub global:         bool,
+    /// human readable description of the error
+    pub message:        String
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = if self.global { "global" } else { "webhook" };
+        write!(f, "Hit the {} rate limit, retry after {:.3}s", scope, self.retry_after.as_secs_f64())
+    }
+}
+
+impl std::error::Error for RateLimitError {}
+
+/// A webhook does not satisfy Discord's requirements
+#[derive(Debug, Clone, Default)]
+pub struct ValidationError {
+    /// every requirement that is violated
+    pub violations: Vec<Violation>
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid webhook")?;
+        for (idx, violation) in self.violations.iter().enumerate() {
+            let separator = if idx == 0 { ": " } else { "; " };
+            write!(f, "{}{}", separator, violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A single violated requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// path to the offending value, e.g. `embeds[2].fields[30]`
+    pub path:       String,
+    /// description of the violated requirement
+    pub message:    String
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// A webhook URL is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlError {
+    /// description of what is wrong with the URL
+    pub message:    String
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid webhook URL: {}", self.message)
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+/// The body of a 429 response
+#[derive(Deserialize)]
+struct RateLimitBody {
+    /// human readable description of the error
+    #[serde(default)]
+    message:        String,
+    /// seconds to wait before retrying
+    retry_after:    f64,
+    /// whether the global rate limit was hit
+    #[serde(default)]
+    global:         bool
+}
+
+/// The body of an error response
+#[derive(Deserialize)]
+struct DiscordErrorBody {
+    /// Discord's JSON error code
+    code:           u64,
+    /// human readable description of the error
+    message:        String,
+    /// nested object containing errors for individual fields
+    errors:         Option<serde_json::Value>
+}
+
+/// Flatten Discord's nested field errors, e.g. `{"embeds": {"0": {"title": {"_errors": [..]}}}}`, into a list
+fn collect_field_errors(value: &serde_json::Value, path: String, out: &mut Vec<FieldError>) {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => return
+    };
+
+    for (key, value) in object {
+        if key == "_errors" {
+            for error in value.as_array().into_iter().flatten() {
+                out.push(FieldError {
+                    path:       path.clone(),
+                    code:       error["code"].as_str().unwrap_or_default().to_string(),
+                    message:    error["message"].as_str().unwrap_or_default().to_string()
+                });
+            }
+        } else {
+            let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            collect_field_errors(value, path, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+    use crate::*;
+
+    #[test]
+    fn transport_error_redacts_token() {
+        let e = reqwest::blocking::get("http://127.0.0.1:9/api/webhooks/123/secret/messages/456").unwrap_err();
+        let e = Error::from(e);
+
+        assert!(!e.to_string().contains("secret"), "{}", e);
+        assert!(!format!("{:?}", e).contains("secret"), "{:?}", e);
+        assert!(e.to_string().contains("/api/webhooks/123/****/messages/456"), "{}", e);
+    }
+
+    #[test]
+    fn discord_error_body() {
+        let body = r#"{"code": 50035, "message": "Invalid Form Body", "errors": {"embeds": {"0": {"title": {"_errors": [{"code": "BASE_TYPE_MAX_LENGTH", "message": "Must be 256 or fewer in length."}]}}}}}"#;
+
+        match Error::from_response(400, None, body.to_string()) {
+            Error::Discord(e) => {
+                assert_eq!(e.status, 400);
+                assert_eq!(e.code, 50035);
+                assert_eq!(e.errors.len(), 1);
+                assert_eq!(e.errors[0].path, "embeds.0.title");
+                assert_eq!(e.errors[0].code, "BASE_TYPE_MAX_LENGTH");
+            },
+            e => panic!("Expected a Discord error, got {:?}", e)
+        }
+    }
+
+    #[test]
+    fn rate_limit_error_body() {
+        let body = r#"{"message": "You are being rate limited.", "retry_after": 1.5, "global": false}"#;
+
+        match Error::from_response(429, None, body.to_string()) {
+            Error::RateLimited(e) => {
+                assert_eq!(e.retry_after, Duration::from_millis(1500));
+                assert!(!e.global);
+            },
+            e => panic!("Expected a rate limit error, got {:?}", e)
+        }
+    }
+
+    #[test]
+    fn status_error_body() {
+        match Error::from_response(502, None, "Bad Gateway".to_string()) {
+            Error::Status(e) => assert_eq!(e.status, 502),
+            e => panic!("Expected a status error, got {:?}", e)
+        }
+    }
+}
@@ -1,9 +1,12 @@
 //! dwbhk is a Rust library for firing a Discord webhook
 //!
 //! ## Features
-//! - `no-panic` Disables `panic!` in the crate
 //! - `blocking` Enables `reqwest`'s `blocking` feature. One can use the blocking methods with the `execute_url_sync` and `execute_sync` on `WebhookRequest`
 //!
+//! ## Validation
+//! Every builder has a `build` and a `try_build` method. `try_build` returns a [ValidationError] listing every
+//! requirement of Discord the value violates, `build` panics with that error instead.
+//!
 //! ## Usage
 //! Example usage:
 //! ```no_run
@@ -42,13 +45,40 @@
 #![warn(clippy::missing_docs_in_private_items)]
 #![warn(clippy::missing_errors_doc)]
 #![warn(clippy::needless_continue)]
+#![allow(clippy::multiple_crate_versions)]
+
+mod error;
+pub use error::*;
 
 mod types;
 pub use types::*;
 
+mod validation;
+pub mod limits;
+
+mod truncation;
+pub use truncation::*;
+
+mod split;
+pub use split::*;
+
+mod ratelimit;
+pub use ratelimit::*;
+
+mod retry;
+pub use retry::*;
+
+mod client;
+pub use client::*;
+
 mod webhook;
 pub use webhook::*;
 
+mod management;
+
+#[cfg(test)]
+mod stub;
+
 #[cfg(test)]
 mod test {
 
@@ -89,4 +119,4 @@ mod test {
         rt.block_on(req.clone().execute_url(&url)).unwrap();
         req.execute_url_sync(&url).unwrap();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+//! Limits Discord imposes on the contents of a message and on webhooks. Lengths are counted in characters
+
+/// Maximum length of a message's content
+pub const CONTENT_LENGTH: usize = 2000;
+/// Maximum number of embeds in a message
+pub const EMBEDS: usize = 10;
+/// Maximum combined length of the title, description, field names and values,
+/// footer text and author name of all embeds in a message
+pub const EMBEDS_TOTAL_LENGTH: usize = 6000;
+/// Maximum length of an embed's title
+pub const EMBED_TITLE_LENGTH: usize = 256;
+/// Maximum length of an embed's description
+pub const EMBED_DESCRIPTION_LENGTH: usize = 4096;
+/// Maximum number of fields in an embed
+pub const EMBED_FIELDS: usize = 25;
+/// Maximum length of an embed field's name
+pub const EMBED_FIELD_NAME_LENGTH: usize = 256;
+/// Maximum length of an embed field's value
+pub const EMBED_FIELD_VALUE_LENGTH: usize = 1024;
+/// Maximum length of an embed footer's text
+pub const EMBED_FOOTER_TEXT_LENGTH: usize = 2048;
+/// Maximum length of an embed author's name
+pub const EMBED_AUTHOR_NAME_LENGTH: usize = 256;
+/// Maximum length of an attachment's description
+pub const ATTACHMENT_DESCRIPTION_LENGTH: usize = 1024;
+/// Maximum number of roles or users in allowed mentions
+pub const ALLOWED_MENTION_IDS: usize = 100;
+/// Maximum length of a webhook's name
+pub const WEBHOOK_NAME_LENGTH: usize = 80;
+/// Maximum length of the name of a thread created by a message
+pub const THREAD_NAME_LENGTH: usize = 100;
+/// Maximum number of tags applied to a forum post
+pub const APPLIED_TAGS: usize = 5;
+/// Maximum length of a poll's question
+pub const POLL_QUESTION_LENGTH: usize = 300;
+/// Maximum number of answers in a poll
+pub const POLL_ANSWERS: usize = 10;
+/// Maximum length of a poll answer's text
+pub const POLL_ANSWER_LENGTH: usize = 55;
+/// Maximum number of hours a poll can be open for, 32 days
+pub const POLL_DURATION_HOURS: u32 = 768;
+/// Maximum number of action rows in a message
+pub const ACTION_ROWS: usize = 5;
+/// Width of an action row, a button takes up one unit and a select menu the whole row
+pub const ACTION_ROW_WIDTH: usize = 5;
+/// Maximum length of a button's label
+pub const BUTTON_LABEL_LENGTH: usize = 80;
+/// Maximum length of a component's custom id
+pub const CUSTOM_ID_LENGTH: usize = 100;
+/// Maximum length of a select menu's placeholder
+pub const SELECT_PLACEHOLDER_LENGTH: usize = 150;
+/// Maximum number of options in a select menu
+pub const SELECT_OPTIONS: usize = 25;
+/// Maximum length of a select option's label, value and description
+pub const SELECT_OPTION_LENGTH: usize = 100;
@@ -0,0 +1,225 @@
+//! Module containing the logic for managing the webhook itself, using its token
+
+use crate::{WebhookInfo, WebhookModify, WebhookUrl, IntoWebhookUrl, WebhookClient, Error};
+use crate::validation::Validate;
+
+impl WebhookInfo {
+    /// Get the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    /// - When the returned webhook could not be deserialized
+    pub async fn get_url<U: IntoWebhookUrl>(url: U) -> Result<WebhookInfo, Error> {
+        WebhookClient::shared().get_webhook(url).await
+    }
+
+    /// Get the webhook with the given `id` and `token`
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    /// - When the returned webhook could not be deserialized
+    pub async fn get<A: AsRef<str>, B: AsRef<str>>(id: A, token: B) -> Result<WebhookInfo, Error> {
+        Self::get_url(WebhookUrl::new(id, token)?).await
+    }
+
+    /// Delete the webhook at the target URL, after which it can no longer be executed
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    pub async fn delete_url<U: IntoWebhookUrl>(url: U) -> Result<(), Error> {
+        WebhookClient::shared().delete_webhook(url).await
+    }
+
+    /// Delete the webhook with the given `id` and `token`, after which it can no longer be executed
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    pub async fn delete<A: AsRef<str>, B: AsRef<str>>(id: A, token: B) -> Result<(), Error> {
+        Self::delete_url(WebhookUrl::new(id, token)?).await
+    }
+}
+
+impl WebhookModify {
+    /// Apply the changes to the webhook at the target URL, returning the modified webhook
+    ///
+    /// # Errors
+    /// - When the changes are invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned webhook could not be deserialized
+    pub async fn execute_url<U: IntoWebhookUrl>(&self, url: U) -> Result<WebhookInfo, Error> {
+        WebhookClient::shared().modify_webhook(self, url).await
+    }
+
+    /// Apply the changes to the webhook with the given `id` and `token`, returning the modified webhook
+    ///
+    /// # Errors
+    /// - When the changes are invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned webhook could not be deserialized
+    pub async fn execute<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<WebhookInfo, Error> {
+        self.execute_url(WebhookUrl::new(id, token)?).await
+    }
+}
+
+impl WebhookClient {
+    /// Get the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    /// - When the returned webhook could not be deserialized
+    pub async fn get_webhook<U: IntoWebhookUrl>(&self, url: U) -> Result<WebhookInfo, Error> {
+        let url = url.into_webhook_url()?;
+        let webhook_url = url.webhook_url(&self.api_url());
+        let response = self.send_request(&url, true, |http| Ok(http.get(&webhook_url))).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Apply the changes to the webhook at the target URL, returning the modified webhook
+    ///
+    /// # Errors
+    /// - When the changes are invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned webhook could not be deserialized
+    pub async fn modify_webhook<U: IntoWebhookUrl>(&self, modify: &WebhookModify, url: U) -> Result<WebhookInfo, Error> {
+        modify.check()?;
+
+        let url = url.into_webhook_url()?;
+        let webhook_url = url.webhook_url(&self.api_url());
+        let response = self.send_request(&url, true, |http| Ok(http.patch(&webhook_url).json(modify))).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Delete the webhook at the target URL, after which it can no longer be executed
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+    pub async fn delete_webhook<U: IntoWebhookUrl>(&self, url: U) -> Result<(), Error> {
+        let url = url.into_webhook_url()?;
+        let webhook_url = url.webhook_url(&self.api_url());
+        self.send_request(&url, true, |http| Ok(http.delete(&webhook_url))).await?;
+        Ok(())
+    }
+}
+
+/// Blocking variants of managing the webhook
+#[cfg(feature = "blocking")]
+mod blocking {
+    use crate::{WebhookInfo, WebhookModify, WebhookUrl, IntoWebhookUrl, BlockingWebhookClient, Error};
+    use crate::validation::Validate;
+
+    impl WebhookInfo {
+        /// Get the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        /// - When the returned webhook could not be deserialized
+        pub fn get_url_sync<U: IntoWebhookUrl>(url: U) -> Result<WebhookInfo, Error> {
+            BlockingWebhookClient::shared().get_webhook(url)
+        }
+
+        /// Get the webhook with the given `id` and `token`
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        /// - When the returned webhook could not be deserialized
+        pub fn get_sync<A: AsRef<str>, B: AsRef<str>>(id: A, token: B) -> Result<WebhookInfo, Error> {
+            Self::get_url_sync(WebhookUrl::new(id, token)?)
+        }
+
+        /// Delete the webhook at the target URL, after which it can no longer be executed
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        pub fn delete_url_sync<U: IntoWebhookUrl>(url: U) -> Result<(), Error> {
+            BlockingWebhookClient::shared().delete_webhook(url)
+        }
+
+        /// Delete the webhook with the given `id` and `token`, after which it can no longer be executed
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        pub fn delete_sync<A: AsRef<str>, B: AsRef<str>>(id: A, token: B) -> Result<(), Error> {
+            Self::delete_url_sync(WebhookUrl::new(id, token)?)
+        }
+    }
+
+    impl WebhookModify {
+        /// Apply the changes to the webhook at the target URL, returning the modified webhook
+        ///
+        /// # Errors
+        /// - When the changes are invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned webhook could not be deserialized
+        pub fn execute_url_sync<U: IntoWebhookUrl>(&self, url: U) -> Result<WebhookInfo, Error> {
+            BlockingWebhookClient::shared().modify_webhook(self, url)
+        }
+
+        /// Apply the changes to the webhook with the given `id` and `token`, returning the modified webhook
+        ///
+        /// # Errors
+        /// - When the changes are invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned webhook could not be deserialized
+        pub fn execute_sync<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<WebhookInfo, Error> {
+            self.execute_url_sync(WebhookUrl::new(id, token)?)
+        }
+    }
+
+    impl BlockingWebhookClient {
+        /// Get the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        /// - When the returned webhook could not be deserialized
+        pub fn get_webhook<U: IntoWebhookUrl>(&self, url: U) -> Result<WebhookInfo, Error> {
+            let url = url.into_webhook_url()?;
+            let webhook_url = url.webhook_url(&self.api_url());
+            let response = self.send_request(&url, true, |http| Ok(http.get(&webhook_url)))?;
+            Ok(response.json()?)
+        }
+
+        /// Apply the changes to the webhook at the target URL, returning the modified webhook
+        ///
+        /// # Errors
+        /// - When the changes are invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned webhook could not be deserialized
+        pub fn modify_webhook<U: IntoWebhookUrl>(&self, modify: &WebhookModify, url: U) -> Result<WebhookInfo, Error> {
+            modify.check()?;
+
+            let url = url.into_webhook_url()?;
+            let webhook_url = url.webhook_url(&self.api_url());
+            let response = self.send_request(&url, true, |http| Ok(http.patch(&webhook_url).json(modify)))?;
+            Ok(response.json()?)
+        }
+
+        /// Delete the webhook at the target URL, after which it can no longer be executed
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the webhook does not exist
+        pub fn delete_webhook<U: IntoWebhookUrl>(&self, url: U) -> Result<(), Error> {
+            let url = url.into_webhook_url()?;
+            let webhook_url = url.webhook_url(&self.api_url());
+            self.send_request(&url, true, |http| Ok(http.delete(&webhook_url)))?;
+            Ok(())
+        }
+    }
+}
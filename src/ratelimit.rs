@@ -0,0 +1,274 @@
+//! Tracking of Discord's rate limits, to delay requests instead of having them rejected
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use reqwest::header::HeaderMap;
+use lazy_static::lazy_static;
+use crate::{Error, RateLimitError};
+
+/// Number of times a request rejected with status 429 is retried by default
+pub const DEFAULT_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Longest wait for a rate limit to reset by default
+pub const DEFAULT_MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    /// Rate limiter shared by every request
+    static ref SHARED_RATE_LIMITER: RateLimiter = RateLimiter::new(DEFAULT_RATE_LIMIT_RETRIES);
+}
+
+/// Tracks the rate limits of webhooks from the `X-RateLimit-*` headers of Discord's responses.
+/// A request to a webhook whose bucket is exhausted, or while the global rate limit is hit, is delayed until the limit resets.
+/// A request which is rejected with status 429 regardless is retried after the `Retry-After` delay,
+/// up to [Self::max_retries] times, after which [crate::Error::RateLimited] is returned.
+/// If the limit resets after more than [Self::max_wait], the request is not delayed, but fails with [crate::Error::RateLimited] right away
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// how often a request rejected with status 429 is retried
+    max_retries:    AtomicU32,
+    /// the longest wait for a rate limit to reset, in milliseconds
+    max_wait:       AtomicU64,
+    /// the known rate limits
+    state:          Mutex<RateLimitState>
+}
+
+/// The known rate limits
+#[derive(Debug, Default)]
+struct RateLimitState {
+    /// rate limit bucket of every webhook, by the webhook's id
+    buckets:        HashMap<String, Bucket>,
+    /// when the global rate limit resets, if it has been hit
+    global_reset:   Option<Instant>
+}
+
+/// A rate limit bucket
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// number of requests which can still be made before the bucket resets
+    remaining:      u64,
+    /// when the bucket resets
+    reset:          Instant
+}
+
+impl RateLimiter {
+    /// Create a rate limiter retrying requests rejected with status 429 up to `max_retries` times,
+    /// waiting up to [DEFAULT_MAX_RATE_LIMIT_WAIT] for a rate limit to reset
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries:    AtomicU32::new(max_retries),
+            max_wait:       AtomicU64::new(millis(DEFAULT_MAX_RATE_LIMIT_WAIT)),
+            state:          Mutex::new(RateLimitState::default())
+        }
+    }
+
+    /// The rate limiter shared by every request, retrying up to [DEFAULT_RATE_LIMIT_RETRIES] times unless configured otherwise
+    pub fn shared() -> &'static RateLimiter {
+        &SHARED_RATE_LIMITER
+    }
+
+    /// How often a request rejected with status 429 is retried
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries.load(Ordering::Relaxed)
+    }
+
+    /// Set how often a request rejected with status 429 is retried. `0` returns the rate limit error right away
+    pub fn set_max_retries(&self, max_retries: u32) {
+        self.max_retries.store(max_retries, Ordering::Relaxed);
+    }
+
+    /// The longest wait for a rate limit to reset, after which a request fails instead
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_millis(self.max_wait.load(Ordering::Relaxed))
+    }
+
+    /// Set the longest wait for a rate limit to reset. If Discord asks to wait longer, the request fails with [crate::Error::RateLimited]
+    pub fn set_max_wait(&self, max_wait: Duration) {
+        self.max_wait.store(millis(max_wait), Ordering::Relaxed);
+    }
+
+    /// Check whether a request may wait `delay` for a rate limit to reset, see [Self::max_wait]
+    ///
+    /// # Errors
+    /// - When `delay` exceeds the longest wait
+    pub(crate) fn check_wait(&self, delay: Duration) -> Result<(), Error> {
+        let max_wait = self.max_wait();
+        if delay <= max_wait {
+            return Ok(());
+        }
+
+        let global = self.lock().global_reset.map(|reset| reset > Instant::now()).unwrap_or_default();
+        Err(Error::RateLimited(RateLimitError {
+            retry_after:    delay,
+            global,
+            message:        format!("The rate limit resets after more than {}s", max_wait.as_secs_f64())
+        }))
+    }
+
+    /// Reserve a request to the webhook with the given id.
+    /// Returns how long to wait before trying again if its bucket is exhausted or the global rate limit is hit,
+    /// or `None` if the request may be send right away
+    pub(crate) fn acquire(&self, webhook_id: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let mut state = self.lock();
+
+        if let Some(reset) = state.global_reset {
+            if reset > now {
+                return Some(reset - now);
+            }
+
+            state.global_reset = None;
+        }
+
+        // Without a known bucket, the request may be send right away
+        let bucket = state.buckets.get_mut(webhook_id)?;
+
+        if bucket.reset <= now {
+            state.buckets.remove(webhook_id);
+            return None;
+        }
+
+        if bucket.remaining == 0 {
+            return Some(bucket.reset - now);
+        }
+
+        bucket.remaining -= 1;
+        None
+    }
+
+    /// Update the rate limits of the webhook with the given id from the status code and headers of a response
+    pub(crate) fn update(&self, webhook_id: &str, status: u16, headers: &HeaderMap) {
+        let now = Instant::now();
+        let mut state = self.lock();
+
+        let remaining = header::<u64>(headers, "x-ratelimit-remaining");
+        let reset_after = header::<f64>(headers, "x-ratelimit-reset-after");
+        if let (Some(remaining), Some(reset_after)) = (remaining, reset_after) {
+            state.buckets.insert(webhook_id.to_string(), Bucket {
+                remaining,
+                reset: now + seconds(reset_after)
+            });
+        }
+
+        if status != 429 {
+            return;
+        }
+
+        let retry_after = header::<f64>(headers, reqwest::header::RETRY_AFTER.as_str())
+            .or(reset_after)
+            .map(seconds)
+            .unwrap_or_else(|| Duration::from_secs(1));
+
+        let global = header::<bool>(headers, "x-ratelimit-global").unwrap_or_default()
+            || headers.get("x-ratelimit-scope").map(|scope| scope == "global").unwrap_or_default();
+
+        if global {
+            state.global_reset = Some(now + retry_after);
+        } else {
+            state.buckets.insert(webhook_id.to_string(), Bucket {
+                remaining:  0,
+                reset:      now + retry_after
+            });
+        }
+    }
+
+    /// Lock the state, which cannot be left inconsistent by a panic, so a poisoned lock is recovered
+    fn lock(&self) -> std::sync::MutexGuard<'_, RateLimitState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_RATE_LIMIT_RETRIES)
+    }
+}
+
+/// Parse the header with the given name, if present
+fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// `duration` in whole milliseconds, saturating at `u64::MAX`
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// The longest wait accepted from a response, effectively forever, while still fitting into a `Duration` and an `Instant`
+const MAX_WAIT_SECS: f64 = u32::MAX as f64;
+
+/// A duration of `secs` seconds, treating negative and invalid values as zero, and capping huge values
+pub(crate) fn seconds(secs: f64) -> Duration {
+    if secs.is_finite() && secs > 0.0 {
+        Duration::from_secs_f64(secs.min(MAX_WAIT_SECS))
+    } else {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+    use crate::*;
+
+    fn rate_limit_headers(headers: &[(&'static str, &'static str)]) -> reqwest::header::HeaderMap {
+        headers.iter()
+            .map(|(name, value)| (reqwest::header::HeaderName::from_static(name), reqwest::header::HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn rate_limit_bucket_exhausted() {
+        let limiter = RateLimiter::new(0);
+        assert!(limiter.acquire("1").is_none());
+
+        limiter.update("1", 204, &rate_limit_headers(&[("x-ratelimit-remaining", "1"), ("x-ratelimit-reset-after", "60")]));
+        assert!(limiter.acquire("1").is_none());
+
+        let delay = limiter.acquire("1").unwrap();
+        assert!(delay > Duration::from_secs(59));
+
+        // Other webhooks have their own bucket
+        assert!(limiter.acquire("2").is_none());
+
+        limiter.update("1", 204, &rate_limit_headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset-after", "0")]));
+        assert!(limiter.acquire("1").is_none());
+    }
+
+    #[test]
+    fn rate_limit_429() {
+        let limiter = RateLimiter::new(0);
+        limiter.update("1", 429, &rate_limit_headers(&[("retry-after", "30")]));
+        assert!(limiter.acquire("1").is_some());
+        assert!(limiter.acquire("2").is_none());
+
+        limiter.update("1", 429, &rate_limit_headers(&[("retry-after", "30"), ("x-ratelimit-global", "true")]));
+        assert!(limiter.acquire("2").is_some());
+
+        assert!(limiter.check_wait(Duration::from_secs(30)).is_ok());
+        limiter.set_max_wait(Duration::from_secs(10));
+        match limiter.check_wait(Duration::from_secs(30)) {
+            Err(Error::RateLimited(e)) => assert!(e.global),
+            result => panic!("Expected a rate limit error, got {:?}", result)
+        }
+    }
+
+    #[test]
+    fn rate_limit_invalid_retry_after() {
+        for retry_after in ["inf", "1e20", "-5"].iter() {
+            let limiter = RateLimiter::new(0);
+            limiter.update("1", 429, &rate_limit_headers(&[("retry-after", retry_after)]));
+            let _ = limiter.acquire("1");
+
+            let err = Error::from_response(429, retry_after.parse().ok(), String::new());
+            assert!(matches!(err, Error::RateLimited(_)));
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Retrying of requests which failed because of a transient error, with exponential backoff
+
+use std::sync::RwLock;
+use std::time::Duration;
+use lazy_static::lazy_static;
+use crate::{ValidationError, Violation};
+use crate::validation::{violation, unwrap_build};
+
+/// Status codes of responses from a gateway or an unavailable Discord, which mean the request was not processed
+const UNPROCESSED_STATUSES: &[u16] = &[502, 503, 504];
+
+lazy_static! {
+    /// Retry policy used by every request
+    static ref SHARED_RETRY_POLICY: RwLock<RetryPolicy> = RwLock::new(RetryPolicy::default());
+}
+
+/// Decides whether, and after how long, a request which failed because of a transient error is retried.
+/// The delay before retry `n` is `base_delay * 2^(n - 1)`, capped at `max_delay`, of which up to a `jitter` fraction is randomly taken off.
+///
+/// Sending a message is not idempotent: when Discord responds with status 500 or the request times out,
+/// the message may have been posted regardless, so retrying it could post the message twice.
+/// Such ambiguous failures are therefore only retried for requests which are safe to repeat, like editing, getting or deleting a message,
+/// unless `retry_non_idempotent` is set. By default, a message whose request failed this way is lost.
+///
+/// Responses with status 502, 503 or 504 come from a gateway or an unavailable Discord, which did not process the request,
+/// and a request which could not connect never reached Discord, so these are safe to retry for every request.
+///
+/// Requests rejected with status 429 are retried by the [crate::RateLimiter], and do not count towards `max_attempts`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// the maximum number of times a request is send, including the first attempt. `1` disables retrying
+    pub max_attempts:           u32,
+    /// the delay before the first retry, which doubles with every following retry
+    pub base_delay:             Duration,
+    /// the maximum delay between two attempts
+    pub max_delay:              Duration,
+    /// the fraction of the delay, between `0.0` and `1.0`, which is randomly taken off, so clients do not retry in lockstep
+    pub jitter:                 f64,
+    /// the status codes of responses which are retried
+    pub retry_statuses:         Vec<u16>,
+    /// whether requests which could not connect to Discord are retried
+    pub retry_connect_errors:   bool,
+    /// whether requests which timed out are retried
+    pub retry_timeouts:         bool,
+    /// whether requests which are not idempotent, like sending a message, are retried after an ambiguous failure,
+    /// a response with status 500 or a timeout, at the risk of the request being processed twice
+    pub retry_non_idempotent:   bool
+}
+
+impl RetryPolicy {
+    /// A policy which never retries a request
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    /// The retry policy used by every request
+    pub fn shared() -> RetryPolicy {
+        SHARED_RETRY_POLICY.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Set the retry policy used by every request
+    pub fn set_shared(policy: RetryPolicy) {
+        *SHARED_RETRY_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// How long to wait before retrying a request which got a response with the given status code, after `attempt` attempts.
+    /// Returns `None` if the request should not be retried
+    pub(crate) fn retry_status(&self, status: u16, attempt: u32, idempotent: bool) -> Option<Duration> {
+        let safe = idempotent || self.retry_non_idempotent || UNPROCESSED_STATUSES.contains(&status);
+        let retryable = self.retry_statuses.contains(&status) && safe;
+        self.retry(retryable, attempt)
+    }
+
+    /// How long to wait before retrying a request which failed with the given error, after `attempt` attempts.
+    /// Returns `None` if the request should not be retried
+    pub(crate) fn retry_error(&self, error: &reqwest::Error, attempt: u32, idempotent: bool) -> Option<Duration> {
+        let retryable = if error.is_connect() {
+            self.retry_connect_errors
+        } else if error.is_timeout() {
+            self.retry_timeouts && (idempotent || self.retry_non_idempotent)
+        } else {
+            false
+        };
+
+        self.retry(retryable, attempt)
+    }
+
+    /// The delay before the next attempt, if the failure is `retryable` and attempts are left
+    fn retry(&self, retryable: bool, attempt: u32) -> Option<Duration> {
+        if !retryable || attempt >= self.max_attempts {
+            return None;
+        }
+
+        // Also guards against a NaN jitter, which would make the delay panic
+        let jitter = if self.jitter > 0.0 { self.jitter.min(1.0) * fastrand::f64() } else { 0.0 };
+        Some(self.backoff(attempt).mul_f64(1.0 - jitter))
+    }
+
+    /// The delay before retry number `retry`, without jitter
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retry up to 2 times, starting after half a second, on server errors, connection errors and timeouts
+    fn default() -> Self {
+        Self {
+            max_attempts:           3,
+            base_delay:             Duration::from_millis(500),
+            max_delay:              Duration::from_secs(10),
+            jitter:                 0.5,
+            retry_statuses:         vec![500, 502, 503, 504],
+            retry_connect_errors:   true,
+            retry_timeouts:         true,
+            retry_non_idempotent:   false
+        }
+    }
+}
+
+/// Builder for RetryPolicy
+#[derive(Default)]
+pub struct RetryPolicyBuilder {
+    /// Inner data
+    inner: RetryPolicy
+}
+
+impl RetryPolicyBuilder {
+    /// Create a new Builder, starting from the default policy
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the maximum number of times a request is send, including the first attempt. `1` disables retrying
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.inner.max_attempts = max_attempts;
+        self
+    }
+
+    /// the delay before the first retry, which doubles with every following retry
+    pub fn set_base_delay(mut self, base_delay: Duration) -> Self {
+        self.inner.base_delay = base_delay;
+        self
+    }
+
+    /// the maximum delay between two attempts
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.inner.max_delay = max_delay;
+        self
+    }
+
+    /// the fraction of the delay, between `0.0` and `1.0`, which is randomly taken off
+    pub fn set_jitter(mut self, jitter: f64) -> Self {
+        self.inner.jitter = jitter;
+        self
+    }
+
+    /// the status codes of responses which are retried
+    pub fn set_retry_statuses<I: IntoIterator<Item = u16>>(mut self, statuses: I) -> Self {
+        self.inner.retry_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// whether requests which could not connect to Discord are retried
+    pub fn set_retry_connect_errors(mut self, retry: bool) -> Self {
+        self.inner.retry_connect_errors = retry;
+        self
+    }
+
+    /// whether requests which timed out are retried
+    pub fn set_retry_timeouts(mut self, retry: bool) -> Self {
+        self.inner.retry_timeouts = retry;
+        self
+    }
+
+    /// whether requests which are not idempotent, like sending a message, are retried after an ambiguous failure,
+    /// at the risk of the request being processed twice
+    pub fn set_retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.inner.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Build the RetryPolicy
+    ///
+    /// # Errors
+    /// - If the maximum number of attempts is zero
+    /// - If the jitter is not between `0.0` and `1.0`
+    /// - If the base delay exceeds the maximum delay
+    pub fn try_build(self) -> Result<RetryPolicy, ValidationError> {
+        let mut violations: Vec<Violation> = Vec::new();
+        if self.inner.max_attempts == 0 {
+            violations.push(violation("max_attempts".to_string(), "Must be at least 1"));
+        }
+
+        if !(0.0..=1.0).contains(&self.inner.jitter) {
+            violations.push(violation("jitter".to_string(), "Must be between 0.0 and 1.0"));
+        }
+
+        if self.inner.base_delay > self.inner.max_delay {
+            violations.push(violation("base_delay".to_string(), "May not exceed the maximum delay"));
+        }
+
+        if !violations.is_empty() {
+            return Err(ValidationError { violations });
+        }
+
+        Ok(self.inner)
+    }
+
+    /// Build the RetryPolicy
+    ///
+    /// # Panics
+    /// If the RetryPolicy is invalid, see [Self::try_build]
+    pub fn build(self) -> RetryPolicy {
+        unwrap_build(self.try_build())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+    use crate::*;
+
+    #[test]
+    fn retry_backoff() {
+        let policy = RetryPolicyBuilder::new()
+            .set_base_delay(Duration::from_secs(1))
+            .set_max_delay(Duration::from_secs(5))
+            .set_jitter(0.0)
+            .set_max_attempts(10)
+            .build();
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(100), Duration::from_secs(5));
+
+        assert_eq!(policy.retry_status(503, 2, true), Some(Duration::from_secs(2)));
+        assert!(policy.retry_status(503, 10, true).is_none());
+        assert!(policy.retry_status(400, 1, true).is_none());
+
+        let jittered = RetryPolicy { jitter: 0.5, ..policy }.retry_status(503, 1, true).unwrap();
+        assert!(jittered > Duration::from_millis(499) && jittered <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry_non_idempotent() {
+        let policy = RetryPolicy::default();
+        assert!(policy.retry_status(500, 1, true).is_some());
+        assert!(policy.retry_status(500, 1, false).is_none());
+        assert!(RetryPolicy { retry_non_idempotent: true, ..policy.clone() }.retry_status(500, 1, false).is_some());
+
+        // The request was not processed, so it is safe to retry
+        assert!(policy.retry_status(503, 1, false).is_some());
+        assert!(RetryPolicy { retry_statuses: vec![500], ..policy.clone() }.retry_status(503, 1, false).is_none());
+
+        // The request never reached Discord, so it is safe to retry
+        let error = reqwest::blocking::get("http://127.0.0.1:9").unwrap_err();
+        assert!(error.is_connect());
+        assert!(policy.retry_error(&error, 1, false).is_some());
+        assert!(RetryPolicy::none().retry_error(&error, 1, false).is_none());
+    }
+
+    #[test]
+    fn invalid_retry_policy() {
+        let err = RetryPolicyBuilder::new()
+            .set_max_attempts(0)
+            .set_jitter(1.5)
+            .set_base_delay(Duration::from_secs(60))
+            .try_build()
+            .unwrap_err();
+
+        let paths = err.violations.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["max_attempts", "jitter", "base_delay"]);
+    }
+}
@@ -0,0 +1,216 @@
+//! Splitting of long message content and many embeds into multiple messages
+
+use crate::{Embed, limits};
+
+/// A code fence
+const CODE_FENCE: &str = "```";
+
+/// Maximum length of a code block's language, longer text following a fence is not treated as a language
+const MAX_LANGUAGE_LENGTH: usize = 32;
+
+/// Split `content` into chunks of at most `limit` characters.
+/// Content is split on line boundaries where possible, otherwise on word boundaries, and as a last resort
+/// on character boundaries. A code block spanning multiple chunks is closed at the end of a chunk,
+/// and reopened with the same language at the start of the next one
+pub fn split_content(content: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut remaining = content;
+    let mut open_fence: Option<String> = None;
+
+    loop {
+        let mut prefix = open_fence.as_ref().map(|fence| format!("{}\n", fence)).unwrap_or_default();
+
+        // Without room to reopen the code block with its language and still close it, the language is dropped
+        if open_fence.is_some() && prefix.chars().count() + CODE_FENCE.len() + 1 >= limit {
+            prefix = format!("{}\n", CODE_FENCE);
+        }
+
+        let prefix_length = prefix.chars().count();
+
+        if prefix_length + remaining.chars().count() <= limit {
+            chunks.push(format!("{}{}", prefix, remaining));
+            break;
+        }
+
+        let available = limit.saturating_sub(prefix_length).max(1);
+        let (mut piece, mut rest) = take_piece(remaining, available);
+
+        // Make room for closing a code block which is still open at the end of the chunk
+        if fence_state(piece, ends_line(remaining, piece), open_fence.clone()).is_some() {
+            let available = limit.saturating_sub(prefix_length + CODE_FENCE.len() + 1).max(1);
+            let (shorter_piece, shorter_rest) = take_piece(remaining, available);
+            piece = shorter_piece;
+            rest = shorter_rest;
+        }
+
+        let fence = fence_state(piece, ends_line(remaining, piece), open_fence.take());
+        match &fence {
+            Some(_) => chunks.push(format!("{}{}\n{}", prefix, piece, CODE_FENCE)),
+            None => chunks.push(format!("{}{}", prefix, piece))
+        }
+
+        open_fence = fence;
+        remaining = rest;
+    }
+
+    chunks
+}
+
+/// Pack `embeds` into as few batches as possible, keeping their order.
+/// Every batch contains at most [limits::EMBEDS] embeds, with at most [limits::EMBEDS_TOTAL_LENGTH] characters combined
+pub fn batch_embeds<'a>(embeds: Vec<Embed<'a>>) -> Vec<Vec<Embed<'a>>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_length = 0;
+
+    for embed in embeds {
+        let length = embed.character_count();
+        if !batch.is_empty() && (batch.len() == limits::EMBEDS || batch_length + length > limits::EMBEDS_TOTAL_LENGTH) {
+            batches.push(std::mem::take(&mut batch));
+            batch_length = 0;
+        }
+
+        batch_length += length;
+        batch.push(embed);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+/// Take a piece of at most `budget` characters from the start of `text`, returning the piece and the text following it
+fn take_piece(text: &str, budget: usize) -> (&str, &str) {
+    let window_end = text.char_indices()
+        .nth(budget)
+        .map(|(idx, _)| idx)
+        .unwrap_or_else(|| text.len());
+    let window = &text[..window_end];
+
+    // The piece may end right before a separator
+    if let Some(separator) = text[window_end..].chars().next().filter(|c| c.is_whitespace()) {
+        return (window, &text[window_end + separator.len_utf8()..]);
+    }
+
+    match split_point(window) {
+        Some((idx, separator)) => (&text[..idx], &text[idx + separator.len_utf8()..]),
+        None => (window, &text[window_end..])
+    }
+}
+
+/// The byte index and character to split `window` on: the last newline, or else the last whitespace.
+/// Returns `None` if the window contains neither, other than at its very start
+fn split_point(window: &str) -> Option<(usize, char)> {
+    let newline = window.rfind('\n').filter(|idx| *idx > 0);
+    if let Some(idx) = newline {
+        return Some((idx, '\n'));
+    }
+
+    window.char_indices()
+        .rev()
+        .find(|(idx, c)| *idx > 0 && c.is_whitespace())
+}
+
+/// Whether `piece`, taken from the start of `text`, ends at a line break
+fn ends_line(text: &str, piece: &str) -> bool {
+    text[piece.len()..].starts_with('\n')
+}
+
+/// Whether a code block is open at the end of `text`, given whether one was open at its start.
+/// `ends_line` tells whether the last line of `text` is complete.
+/// Returns the fence opening the code block, including its language, if there is one
+fn fence_state(text: &str, ends_line: bool, mut open_fence: Option<String>) -> Option<String> {
+    let mut rest = text;
+    while let Some(idx) = rest.find(CODE_FENCE) {
+        rest = &rest[idx + CODE_FENCE.len()..];
+
+        open_fence = match open_fence {
+            Some(_) => None,
+            None => {
+                // The language is the rest of the line, if the line is complete and a short identifier
+                let line = match rest.find('\n') {
+                    Some(end) => Some(&rest[..end]),
+                    None if ends_line => Some(rest),
+                    None => None
+                };
+
+                match line {
+                    Some(language) if is_language(language) => Some(format!("{}{}", CODE_FENCE, language)),
+                    _ => Some(CODE_FENCE.to_string())
+                }
+            }
+        };
+    }
+
+    open_fence
+}
+
+/// Whether `text` following an opening fence is a code block's language, e.g. `rust` or `c++`
+fn is_language(text: &str) -> bool {
+    !text.is_empty()
+        && text.chars().count() <= MAX_LANGUAGE_LENGTH
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || "+-_#.".contains(c))
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn split_on_lines_then_words_then_chars() {
+        assert_eq!(split_content("aaaa\nbbbb\ncc dd", 10), vec!["aaaa\nbbbb", "cc dd"]);
+        assert_eq!(split_content("aaa bbb ccc ddd", 10), vec!["aaa bbb", "ccc ddd"]);
+        assert_eq!(split_content("aaaaaaaaaaaa", 10), vec!["aaaaaaaaaa", "aa"]);
+    }
+
+    #[test]
+    fn split_keeps_code_fences_balanced() {
+        let content = "Stack trace:\n```rust\nline one\nline two\nline three\n```\nDone";
+        let chunks = split_content(content, 32);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 32);
+            assert_eq!(chunk.matches("```").count() % 2, 0, "Unbalanced chunk {:?}", chunk);
+        }
+
+        assert!(chunks[1].starts_with("```rust\n"));
+    }
+
+    #[test]
+    fn split_long_fenced_line() {
+        let content = format!("```{}```", "x".repeat(3000));
+        let chunks = split_content(&content, 2000);
+
+        assert!(chunks.len() <= 3, "{} chunks", chunks.len());
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 2000);
+        }
+
+        assert!(chunks[1..].iter().all(|chunk| chunk.starts_with("```\n")), "The code was used as a language");
+
+        let content = format!("```{}\n{}\n```", "l".repeat(12), "code ".repeat(10));
+        for chunk in split_content(&content, 16) {
+            assert!(chunk.chars().count() <= 16, "{:?}", chunk);
+        }
+    }
+
+    #[test]
+    fn batch_embeds_by_count_and_length() {
+        let small = EmbedBuilder::new()
+            .set_title("Small")
+            .build();
+        let large = EmbedBuilder::new()
+            .set_description("a".repeat(4000))
+            .build();
+
+        let batches = batch_embeds(vec![small.clone(); 25]);
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![10, 10, 5]);
+
+        let batches = batch_embeds(vec![small.clone(), large.clone(), large, small]);
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 2]);
+    }
+}
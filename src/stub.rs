@@ -0,0 +1,62 @@
+//! A stub of Discord's API, for testing the clients without reaching Discord
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::{WebhookClientBuilder, RetryPolicyBuilder, RateLimiter};
+
+/// Response to executing a webhook with `wait=true`, or to getting a message
+pub(crate) const STUB_MESSAGE: &str = "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: 79\r\n\r\n\
+    {\"id\": \"2\", \"channel_id\": \"3\", \"timestamp\": \"2021-10-01T12:00:00.000000+00:00\"}";
+
+/// Response of an unavailable Discord, which did not process the request
+pub(crate) const STUB_UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+
+/// Response to a request which may or may not have been processed
+pub(crate) const STUB_SERVER_ERROR: &str = "HTTP/1.1 500 Internal Server Error\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+
+/// Serve the given raw HTTP responses, one per connection, on a local port.
+/// Returns a builder for a client sending its requests to the server, and a handle yielding every request received,
+/// with the request line and headers in lowercase.
+///
+/// The client has its own rate limiter, which does not retry requests rejected with status 429, and retries other failures without waiting
+pub(crate) fn stub_client(responses: Vec<&'static str>) -> (WebhookClientBuilder, JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api", listener.local_addr().unwrap()).parse().unwrap();
+
+    let handle = std::thread::spawn(move || {
+        responses.into_iter().map(|response| {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+
+                head.push_str(&line.to_lowercase());
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            head + &String::from_utf8_lossy(&body)
+        }).collect()
+    });
+
+    let builder = WebhookClientBuilder::new()
+        .set_base_url(base_url)
+        .set_retry_policy(RetryPolicyBuilder::new().set_base_delay(Duration::from_millis(1)).set_max_delay(Duration::from_millis(1)).build())
+        .set_rate_limiter(Arc::new(RateLimiter::new(0)));
+
+    (builder, handle)
+}
@@ -0,0 +1,159 @@
+//! Shortening of text exceeding Discord's limits
+
+use std::borrow::Cow;
+
+/// Policy for shortening text which exceeds Discord's limits, instead of rejecting it.
+/// Text is cut on a character boundary, after which the ellipsis is appended
+#[derive(Clone, Debug)]
+pub struct TruncationPolicy {
+    /// marker appended to truncated text, counting towards the limit
+    pub ellipsis:   String
+}
+
+impl Default for TruncationPolicy {
+    fn default() -> Self {
+        Self { ellipsis: "…".to_string() }
+    }
+}
+
+impl TruncationPolicy {
+    /// Create a policy marking truncated text with `ellipsis`
+    pub fn new<S: AsRef<str>>(ellipsis: S) -> Self {
+        Self { ellipsis: ellipsis.as_ref().to_string() }
+    }
+
+    /// Shorten `text` to at most `limit` characters, including the ellipsis.
+    /// Returns `None` if the text does not exceed the limit
+    pub fn truncate(&self, text: &str, limit: usize) -> Option<String> {
+        if text.chars().count() <= limit {
+            return None;
+        }
+
+        let ellipsis_length = self.ellipsis.chars().count();
+        if ellipsis_length >= limit {
+            return Some(text.chars().take(limit).collect());
+        }
+
+        let mut truncated = text.chars().take(limit - ellipsis_length).collect::<String>();
+        truncated.push_str(&self.ellipsis);
+        Some(truncated)
+    }
+
+    /// Shorten `text` in place to at most `limit` characters
+    pub(crate) fn truncate_string(&self, text: &mut String, limit: usize) {
+        if let Some(truncated) = self.truncate(text, limit) {
+            *text = truncated;
+        }
+    }
+
+    /// Shorten `text` in place to at most `limit` characters, only taking ownership if it has to be shortened
+    pub(crate) fn truncate_cow(&self, text: &mut Cow<'_, str>, limit: usize) {
+        if let Some(truncated) = self.truncate(text, limit) {
+            *text = Cow::Owned(truncated);
+        }
+    }
+}
+
+/// The largest length every text has to be shortened to, so that texts of `lengths` fit in `budget` characters combined.
+/// Only the largest texts are shortened, to the same length.
+/// Returns `None` if the texts already fit
+pub(crate) fn budget_cap(lengths: &[usize], budget: usize) -> Option<usize> {
+    let fits = |cap: usize| lengths.iter().map(|length| (*length).min(cap)).sum::<usize>() <= budget;
+
+    let longest = lengths.iter().copied().max().unwrap_or_default();
+    if fits(longest) {
+        return None;
+    }
+
+    let (mut low, mut high) = (0, longest);
+    while low < high {
+        let mid = high - (high - low) / 2;
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Some(low)
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn truncate_on_char_boundary() {
+        let policy = TruncationPolicy::new("...");
+        assert_eq!(policy.truncate("héllo wörld", 8), Some("héllo...".to_string()));
+        assert_eq!(policy.truncate("héllo", 5), None);
+    }
+
+    #[test]
+    fn truncate_content_and_embed() {
+        let content = "a".repeat(2500);
+        let webhook = WebhookBuilder::new()
+            .set_content(&content)
+            .set_truncation(TruncationPolicy::default())
+            .build();
+
+        let content = webhook.content.unwrap();
+        assert_eq!(content.chars().count(), limits::CONTENT_LENGTH);
+        assert!(content.ends_with('…'));
+
+        let embed = EmbedBuilder::new()
+            .set_title("a".repeat(300))
+            .set_description("b".repeat(5000))
+            .set_truncation(TruncationPolicy::default())
+            .build();
+
+        assert_eq!(embed.title.unwrap().chars().count(), limits::EMBED_TITLE_LENGTH);
+        assert_eq!(embed.description.unwrap().chars().count(), limits::EMBED_DESCRIPTION_LENGTH);
+    }
+
+    #[test]
+    fn truncate_largest_fields_first() {
+        let embed = EmbedBuilder::new()
+            .set_title("Title")
+            .set_description("a".repeat(4000))
+            .build();
+
+        let webhook = WebhookBuilder::new()
+            .set_embeds(vec![embed.clone(), embed])
+            .set_truncation(TruncationPolicy::default())
+            .build();
+
+        let embeds = webhook.embeds.unwrap();
+        assert_eq!(embeds[0].title.as_deref(), Some("Title"));
+        assert_eq!(embeds.iter().map(Embed::character_count).sum::<usize>(), limits::EMBEDS_TOTAL_LENGTH);
+    }
+
+    #[test]
+    fn truncate_split_embed() {
+        let embed = Embed {
+            fields: Some(vec![
+                EmbedField {
+                    name:   "Name".to_string(),
+                    value:  "a".repeat(1024),
+                    inline: None
+                };
+                25
+            ]),
+            ..Default::default()
+        };
+
+        let webhook = WebhookBuilder::new()
+            .set_embeds(vec![embed])
+            .set_truncation(TruncationPolicy::default())
+            .set_split(true)
+            .build();
+
+        // Embeds which are split do not share a budget, but each has to fit into a message
+        let embeds = webhook.embeds.as_ref().unwrap();
+        assert_eq!(embeds[0].character_count(), limits::EMBEDS_TOTAL_LENGTH);
+
+        let req = WebhookRequestBuilder::new().set_data(webhook).build();
+        assert!(req.validate().is_ok());
+    }
+}
@@ -0,0 +1,256 @@
+//! Structs related to files uploaded alongside a webhook message
+
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeSeq;
+use crate::{ValidationError, Violation};
+use crate::validation::{Validate, field_path, violation, check_length, unwrap_build};
+use crate::limits;
+
+/// The URL scheme used to refer to uploaded files from within an embed
+pub(crate) const ATTACHMENT_URL_SCHEME: &str = "attachment://";
+
+/// A file to be uploaded with the message
+#[derive(Default, Clone, Debug)]
+pub struct Attachment {
+    /// the contents of the file
+    pub data:           Vec<u8>,
+    /// name of the file
+    pub file_name:      String,
+    /// the file's media type, e.g. `image/png`. If not set, it is guessed from the file name
+    pub content_type:   Option<String>,
+    /// description (alt text) for the file (up to 1024 characters)
+    pub description:    Option<String>,
+    /// whether the file should be hidden behind a spoiler
+    pub spoiler:        bool
+}
+
+impl Attachment {
+    /// The name of the file as it is uploaded to Discord.
+    /// Characters other than ASCII letters, digits, `.`, `-` and `_` are replaced with `_`, as Discord would change them itself,
+    /// breaking the `attachment://` URLs referring to the file. Spoilered files are prefixed with `SPOILER_`
+    pub fn upload_file_name(&self) -> String {
+        let file_name = self.file_name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+            .collect::<String>();
+
+        if self.spoiler {
+            format!("SPOILER_{}", file_name)
+        } else {
+            file_name
+        }
+    }
+
+    /// The URL through which embeds in the same message can refer to this file, i.e. `attachment://filename`
+    pub fn attachment_url(&self) -> String {
+        format!("{}{}", ATTACHMENT_URL_SCHEME, self.upload_file_name())
+    }
+
+    /// The file's media type. If none was set, it is guessed from the file name
+    pub fn mime_type(&self) -> String {
+        match &self.content_type {
+            Some(content_type) => content_type.clone(),
+            None => mime_guess::from_path(&self.file_name).first_or_octet_stream().to_string()
+        }
+    }
+}
+
+/// Builder for Attachment
+#[derive(Default)]
+pub struct AttachmentBuilder {
+    /// Inner data
+    inner: Attachment
+}
+
+impl AttachmentBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the contents of the file
+    pub fn set_data<D: Into<Vec<u8>>>(mut self, data: D) -> Self {
+        self.inner.data = data.into();
+        self
+    }
+
+    /// name of the file
+    pub fn set_file_name<S: AsRef<str>>(mut self, file_name: S) -> Self {
+        self.inner.file_name = file_name.as_ref().to_string();
+        self
+    }
+
+    /// the file's media type, e.g. `image/png`
+    pub fn set_content_type<S: AsRef<str>>(mut self, content_type: S) -> Self {
+        self.inner.content_type = Some(content_type.as_ref().to_string());
+        self
+    }
+
+    /// description (alt text) for the file (up to 1024 characters)
+    pub fn set_description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.inner.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// whether the file should be hidden behind a spoiler
+    pub fn set_spoiler(mut self, spoiler: bool) -> Self {
+        self.inner.spoiler = spoiler;
+        self
+    }
+
+    /// Build the Attachment
+    ///
+    /// # Errors
+    /// - If no file name has been set
+    /// - If the content type is not a valid media type
+    pub fn try_build(self) -> Result<Attachment, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the Attachment
+    ///
+    /// # Panics
+    /// If the Attachment is invalid, see [Self::try_build]
+    pub fn build(self) -> Attachment {
+        unwrap_build(self.try_build())
+    }
+}
+
+impl Validate for Attachment {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.file_name.is_empty() {
+            violations.push(violation(field_path(path, "file_name"), "An attachment must have a file name"));
+        }
+
+        if self.mime_type().parse::<mime_guess::mime::Mime>().is_err() {
+            violations.push(violation(field_path(path, "content_type"), format!("'{}' is not a valid media type", self.mime_type())));
+        }
+
+        if let Some(description) = &self.description {
+            check_length(description, limits::ATTACHMENT_DESCRIPTION_LENGTH, field_path(path, "description"), violations);
+        }
+    }
+}
+
+/// The partial attachment object as it appears in the `attachments` array of the payload.
+/// `id` refers to the `files[id]` part the file is uploaded in
+#[derive(Serialize)]
+pub(crate) struct PartialAttachment<'a> {
+    /// index of the file in the multipart body
+    id:             usize,
+    /// name of the file
+    filename:       String,
+    /// description (alt text) for the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description:    Option<&'a str>
+}
+
+impl<'a> PartialAttachment<'a> {
+    /// The partial attachment of `attachment`, uploaded in the `files[id]` part
+    fn new(id: usize, attachment: &'a Attachment) -> Self {
+        Self {
+            id,
+            filename:       attachment.upload_file_name(),
+            description:    attachment.description.as_deref()
+        }
+    }
+}
+
+/// An entry of the `attachments` array when editing a message
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum EditAttachment<'a> {
+    /// an attachment of the message to keep, by its id
+    Keep {
+        /// attachment id
+        id: &'a str
+    },
+    /// a file uploaded with the edit
+    Upload(PartialAttachment<'a>)
+}
+
+/// Serialize a list of attachments into Discord's `attachments` array,
+/// where the n-th attachment is uploaded in the `files[n]` part
+pub(crate) fn serialize_attachments<S: Serializer>(attachments: &Option<Vec<Attachment>>, serializer: S) -> Result<S::Ok, S::Error> {
+    let attachments = match attachments {
+        Some(attachments) => attachments,
+        None => return serializer.serialize_none()
+    };
+
+    let mut seq = serializer.serialize_seq(Some(attachments.len()))?;
+    for (id, attachment) in attachments.iter().enumerate() {
+        seq.serialize_element(&PartialAttachment::new(id, attachment))?;
+    }
+
+    seq.end()
+}
+
+/// Discord's `attachments` array when editing a message, containing the attachments of the message to `keep`
+/// followed by the files to be uploaded, where the n-th upload is uploaded in the `files[n]` part
+pub(crate) fn edit_attachments<'a>(keep: &'a [String], uploads: &'a [Attachment]) -> Vec<EditAttachment<'a>> {
+    let keep = keep.iter()
+        .map(|id| EditAttachment::Keep { id });
+    let uploads = uploads.iter()
+        .enumerate()
+        .map(|(id, attachment)| EditAttachment::Upload(PartialAttachment::new(id, attachment)));
+
+    keep.chain(uploads).collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn attachments_in_payload() {
+        let webhook = WebhookBuilder::new()
+            .set_attachments(vec![
+                AttachmentBuilder::new()
+                    .set_file_name("build.log")
+                    .set_data("Hello world!")
+                    .build(),
+                AttachmentBuilder::new()
+                    .set_file_name("screenshot.png")
+                    .set_data(vec![0u8; 8])
+                    .set_description("A screenshot")
+                    .set_spoiler(true)
+                    .build()
+            ])
+            .build();
+
+        let payload = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(payload["attachments"], serde_json::json!([
+            { "id": 0, "filename": "build.log" },
+            { "id": 1, "filename": "SPOILER_screenshot.png", "description": "A screenshot" }
+        ]));
+    }
+
+    #[test]
+    fn sanitized_file_names() {
+        let attachment = AttachmentBuilder::new()
+            .set_file_name("build log (2).txt")
+            .set_data("Hello world!")
+            .build();
+        assert_eq!(attachment.attachment_url(), "attachment://build_log__2_.txt");
+        assert_eq!(attachment.mime_type(), "text/plain");
+
+        let attachment = AttachmentBuilder::new()
+            .set_file_name("résumé.pdf")
+            .set_spoiler(true)
+            .build();
+        assert_eq!(attachment.upload_file_name(), "SPOILER_r_sum_.pdf");
+    }
+
+    #[test]
+    fn invalid_content_type() {
+        let error = AttachmentBuilder::new()
+            .set_file_name("build.log")
+            .set_content_type("not a media type")
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations.len(), 1);
+        assert_eq!(error.violations[0].path, "content_type");
+    }
+}
@@ -0,0 +1,820 @@
+//! Structs related to the structure of Discord message components
+
+use std::collections::HashSet;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error as _;
+use crate::{ValidationError, Violation};
+use crate::validation::{Validate, field_path, index_path, violation, check_url_scheme, check_length, unwrap_build};
+use crate::limits;
+
+/// URL schemes supported for link buttons
+const LINK_URL_SCHEMES: &[&str] = &["http://", "https://", "discord://"];
+
+/// A row of components in a message.
+/// A row holds up to 5 buttons, or a single select menu.
+///
+/// Webhooks which are not owned by an application can only send link buttons, interactive components
+/// require the webhook to be created by an application
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ActionRow {
+    /// type of component (always 1 for action rows)
+    pub r#type:         u8,
+    /// the components in the row
+    pub components:     Vec<ActionRowComponent>
+}
+
+impl Default for ActionRow {
+    fn default() -> Self {
+        Self {
+            r#type:     1,
+            components: Vec::new()
+        }
+    }
+}
+
+/// Builder for ActionRow
+#[derive(Default)]
+pub struct ActionRowBuilder {
+    /// Inner data
+    inner: ActionRow
+}
+
+impl ActionRowBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the components in the row (up to 5 buttons, or a single select menu)
+    pub fn set_components(mut self, components: Vec<ActionRowComponent>) -> Self {
+        self.inner.components = components;
+        self
+    }
+
+    /// add a button to the row
+    pub fn add_button(mut self, button: Button) -> Self {
+        self.inner.components.push(ActionRowComponent::Button(button));
+        self
+    }
+
+    /// add a select menu to the row, which must be the only component in the row
+    pub fn add_select_menu(mut self, select_menu: SelectMenu) -> Self {
+        self.inner.components.push(ActionRowComponent::SelectMenu(select_menu));
+        self
+    }
+
+    /// Build the ActionRow
+    ///
+    /// # Errors
+    /// - If the row is empty, or its components take up more than the width of the row
+    /// - If any of the components is invalid
+    pub fn try_build(self) -> Result<ActionRow, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the ActionRow
+    ///
+    /// # Panics
+    /// If the ActionRow is invalid, see [Self::try_build]
+    pub fn build(self) -> ActionRow {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// A component within an action row
+#[derive(Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ActionRowComponent {
+    /// a button
+    Button(Button),
+    /// a select menu
+    SelectMenu(SelectMenu)
+}
+
+impl ActionRowComponent {
+    /// The part of the row's width the component takes up
+    fn width(&self) -> usize {
+        match self {
+            Self::Button(_) => 1,
+            Self::SelectMenu(_) => limits::ACTION_ROW_WIDTH
+        }
+    }
+
+    /// The developer-defined identifier of the component, if it has one
+    fn custom_id(&self) -> Option<&str> {
+        match self {
+            Self::Button(button) => button.custom_id.as_deref(),
+            Self::SelectMenu(select_menu) => Some(&select_menu.custom_id)
+        }
+    }
+}
+
+/// Deserialize the component its `type` refers to
+impl<'de> Deserialize<'de> for ActionRowComponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let r#type = value.get("type")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+
+        match r#type {
+            2 => Button::deserialize(value).map(Self::Button),
+            3 | 5 | 6 | 7 | 8 => SelectMenu::deserialize(value).map(Self::SelectMenu),
+            r#type => return Err(D::Error::custom(format!("unsupported component type {}", r#type)))
+        }.map_err(D::Error::custom)
+    }
+}
+
+impl From<Button> for ActionRowComponent {
+    fn from(button: Button) -> Self {
+        Self::Button(button)
+    }
+}
+
+impl From<SelectMenu> for ActionRowComponent {
+    fn from(select_menu: SelectMenu) -> Self {
+        Self::SelectMenu(select_menu)
+    }
+}
+
+/// A button
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Button {
+    /// type of component (always 2 for buttons)
+    pub r#type:         u8,
+    /// the style of the button
+    pub style:          ButtonStyle,
+    /// text that appears on the button (up to 80 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label:          Option<String>,
+    /// emoji that appears on the button
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji:          Option<ComponentEmoji>,
+    /// developer-defined identifier for the button (up to 100 characters), required for all but link buttons
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_id:      Option<String>,
+    /// url for link buttons
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url:            Option<String>,
+    /// whether the button is disabled (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled:       Option<bool>
+}
+
+impl Default for Button {
+    fn default() -> Self {
+        Self {
+            r#type:     2,
+            style:      ButtonStyle::Primary,
+            label:      None,
+            emoji:      None,
+            custom_id:  None,
+            url:        None,
+            disabled:   None
+        }
+    }
+}
+
+/// Builder for Button
+#[derive(Default)]
+pub struct ButtonBuilder {
+    /// Inner data
+    inner: Button
+}
+
+impl ButtonBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the style of the button, defaults to [ButtonStyle::Primary]
+    pub fn set_style(mut self, style: ButtonStyle) -> Self {
+        self.inner.style = style;
+        self
+    }
+
+    /// text that appears on the button (up to 80 characters)
+    pub fn set_label<S: AsRef<str>>(mut self, label: S) -> Self {
+        self.inner.label = Some(label.as_ref().to_string());
+        self
+    }
+
+    /// emoji that appears on the button
+    pub fn set_emoji(mut self, emoji: ComponentEmoji) -> Self {
+        self.inner.emoji = Some(emoji);
+        self
+    }
+
+    /// developer-defined identifier for the button (up to 100 characters)
+    pub fn set_custom_id<S: AsRef<str>>(mut self, custom_id: S) -> Self {
+        self.inner.custom_id = Some(custom_id.as_ref().to_string());
+        self
+    }
+
+    /// url the button opens, which makes it a link button
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
+        self.inner.style = ButtonStyle::Link;
+        self.inner.url = Some(url.as_ref().to_string());
+        self
+    }
+
+    /// whether the button is disabled
+    pub fn set_disabled(mut self, disabled: bool) -> Self {
+        self.inner.disabled = Some(disabled);
+        self
+    }
+
+    /// Build the Button
+    ///
+    /// # Errors
+    /// - If neither a label nor an emoji has been set, or the label exceeds 80 characters
+    /// - If a link button has no http(s) or discord url, or has a custom id
+    /// - If any other button has no custom id, or has a url
+    pub fn try_build(self) -> Result<Button, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the Button
+    ///
+    /// # Panics
+    /// If the Button is invalid, see [Self::try_build]
+    pub fn build(self) -> Button {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// The style of a button
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonStyle {
+    /// blurple, requires a custom id
+    Primary,
+    /// grey, requires a custom id
+    Secondary,
+    /// green, requires a custom id
+    Success,
+    /// red, requires a custom id
+    Danger,
+    /// grey, navigates to a URL
+    Link
+}
+
+impl Serialize for ButtonStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Self::Primary => 1,
+            Self::Secondary => 2,
+            Self::Success => 3,
+            Self::Danger => 4,
+            Self::Link => 5
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ButtonStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Self::Primary),
+            2 => Ok(Self::Secondary),
+            3 => Ok(Self::Success),
+            4 => Ok(Self::Danger),
+            5 => Ok(Self::Link),
+            style => Err(D::Error::custom(format!("unsupported button style {}", style)))
+        }
+    }
+}
+
+/// A select menu
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SelectMenu {
+    /// the type of select menu
+    pub r#type:         SelectMenuType,
+    /// developer-defined identifier for the select menu (up to 100 characters)
+    pub custom_id:      String,
+    /// specified choices in a string select menu (up to 25)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options:        Option<Vec<SelectOption>>,
+    /// placeholder text if nothing is selected (up to 150 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder:    Option<String>,
+    /// minimum number of items that must be chosen (defaults to 1, up to 25)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_values:     Option<u8>,
+    /// maximum number of items that can be chosen (defaults to 1, up to 25)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_values:     Option<u8>,
+    /// whether the select menu is disabled (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled:       Option<bool>
+}
+
+impl Default for SelectMenu {
+    fn default() -> Self {
+        Self {
+            r#type:         SelectMenuType::String,
+            custom_id:      String::new(),
+            options:        None,
+            placeholder:    None,
+            min_values:     None,
+            max_values:     None,
+            disabled:       None
+        }
+    }
+}
+
+/// Builder for SelectMenu
+#[derive(Default)]
+pub struct SelectMenuBuilder {
+    /// Inner data
+    inner: SelectMenu
+}
+
+impl SelectMenuBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the type of select menu, defaults to [SelectMenuType::String]
+    pub fn set_type(mut self, r#type: SelectMenuType) -> Self {
+        self.inner.r#type = r#type;
+        self
+    }
+
+    /// developer-defined identifier for the select menu (up to 100 characters)
+    pub fn set_custom_id<S: AsRef<str>>(mut self, custom_id: S) -> Self {
+        self.inner.custom_id = custom_id.as_ref().to_string();
+        self
+    }
+
+    /// specified choices in a string select menu (up to 25)
+    pub fn set_options(mut self, options: Vec<SelectOption>) -> Self {
+        self.inner.options = Some(options);
+        self
+    }
+
+    /// add a choice to a string select menu
+    pub fn add_option(mut self, option: SelectOption) -> Self {
+        self.inner.options.get_or_insert_with(Vec::new).push(option);
+        self
+    }
+
+    /// placeholder text if nothing is selected (up to 150 characters)
+    pub fn set_placeholder<S: AsRef<str>>(mut self, placeholder: S) -> Self {
+        self.inner.placeholder = Some(placeholder.as_ref().to_string());
+        self
+    }
+
+    /// minimum number of items that must be chosen (up to 25)
+    pub fn set_min_values(mut self, min_values: u8) -> Self {
+        self.inner.min_values = Some(min_values);
+        self
+    }
+
+    /// maximum number of items that can be chosen (1 to 25)
+    pub fn set_max_values(mut self, max_values: u8) -> Self {
+        self.inner.max_values = Some(max_values);
+        self
+    }
+
+    /// whether the select menu is disabled
+    pub fn set_disabled(mut self, disabled: bool) -> Self {
+        self.inner.disabled = Some(disabled);
+        self
+    }
+
+    /// Build the SelectMenu
+    ///
+    /// # Errors
+    /// - If the custom id is empty or exceeds 100 characters
+    /// - If a string select menu has no options or more than 25, or another type of select menu has options
+    /// - If the placeholder exceeds 150 characters
+    /// - If the minimum or maximum number of values is out of range
+    /// - If any of the options is invalid
+    pub fn try_build(self) -> Result<SelectMenu, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the SelectMenu
+    ///
+    /// # Panics
+    /// If the SelectMenu is invalid, see [Self::try_build]
+    pub fn build(self) -> SelectMenu {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// The type of a select menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectMenuType {
+    /// select menu for picking from defined text options
+    String,
+    /// select menu for users
+    User,
+    /// select menu for roles
+    Role,
+    /// select menu for mentionables (users and roles)
+    Mentionable,
+    /// select menu for channels
+    Channel
+}
+
+impl Serialize for SelectMenuType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Self::String => 3,
+            Self::User => 5,
+            Self::Role => 6,
+            Self::Mentionable => 7,
+            Self::Channel => 8
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SelectMenuType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            3 => Ok(Self::String),
+            5 => Ok(Self::User),
+            6 => Ok(Self::Role),
+            7 => Ok(Self::Mentionable),
+            8 => Ok(Self::Channel),
+            r#type => Err(D::Error::custom(format!("unsupported select menu type {}", r#type)))
+        }
+    }
+}
+
+/// A choice in a string select menu
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct SelectOption {
+    /// user-facing name of the option (up to 100 characters)
+    pub label:          String,
+    /// developer-defined value of the option (up to 100 characters)
+    pub value:          String,
+    /// additional description of the option (up to 100 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description:    Option<String>,
+    /// emoji shown with the option
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji:          Option<ComponentEmoji>,
+    /// whether the option is selected by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default:        Option<bool>
+}
+
+/// Builder for SelectOption
+#[derive(Default)]
+pub struct SelectOptionBuilder {
+    /// Inner data
+    inner: SelectOption
+}
+
+impl SelectOptionBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// user-facing name of the option (up to 100 characters)
+    pub fn set_label<S: AsRef<str>>(mut self, label: S) -> Self {
+        self.inner.label = label.as_ref().to_string();
+        self
+    }
+
+    /// developer-defined value of the option (up to 100 characters)
+    pub fn set_value<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.inner.value = value.as_ref().to_string();
+        self
+    }
+
+    /// additional description of the option (up to 100 characters)
+    pub fn set_description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.inner.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// emoji shown with the option
+    pub fn set_emoji(mut self, emoji: ComponentEmoji) -> Self {
+        self.inner.emoji = Some(emoji);
+        self
+    }
+
+    /// whether the option is selected by default
+    pub fn set_default(mut self, default: bool) -> Self {
+        self.inner.default = Some(default);
+        self
+    }
+
+    /// Build the SelectOption
+    ///
+    /// # Errors
+    /// - If the label or value is empty or exceeds 100 characters
+    /// - If the description exceeds 100 characters
+    pub fn try_build(self) -> Result<SelectOption, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the SelectOption
+    ///
+    /// # Panics
+    /// If the SelectOption is invalid, see [Self::try_build]
+    pub fn build(self) -> SelectOption {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// The emoji of a button or select option
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ComponentEmoji {
+    /// a custom emoji of a guild
+    Custom {
+        /// the id of the emoji
+        id: String
+    },
+    /// a unicode emoji, e.g. "📈"
+    Unicode {
+        /// the emoji itself
+        name: String
+    }
+}
+
+impl ComponentEmoji {
+    /// The custom emoji with the given id
+    pub fn custom<S: AsRef<str>>(id: S) -> Self {
+        Self::Custom { id: id.as_ref().to_string() }
+    }
+
+    /// The given unicode emoji
+    pub fn unicode<S: AsRef<str>>(emoji: S) -> Self {
+        Self::Unicode { name: emoji.as_ref().to_string() }
+    }
+}
+
+/// Check the action rows of a message: their number, and that no two components share a custom id
+pub(crate) fn collect_component_violations(rows: &[ActionRow], path: &str, violations: &mut Vec<Violation>) {
+    if rows.len() > limits::ACTION_ROWS {
+        violations.push(violation(path.to_string(), format!("There may be up to {} action rows", limits::ACTION_ROWS)));
+    }
+
+    let mut custom_ids = HashSet::new();
+    for (idx, row) in rows.iter().enumerate() {
+        let row_path = index_path(path, idx);
+        row.collect_violations(&row_path, violations);
+
+        for (component_idx, component) in row.components.iter().enumerate() {
+            if let Some(custom_id) = component.custom_id() {
+                if !custom_ids.insert(custom_id) {
+                    let component_path = index_path(&field_path(&row_path, "components"), component_idx);
+                    violations.push(violation(field_path(&component_path, "custom_id"), format!("'{}' is used by multiple components", custom_id)));
+                }
+            }
+        }
+    }
+}
+
+impl Validate for ActionRow {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.r#type != 1 {
+            violations.push(violation(field_path(path, "type"), format!("An action row must have type 1, not {}", self.r#type)));
+        }
+
+        let components_path = field_path(path, "components");
+        if self.components.is_empty() {
+            violations.push(violation(components_path.clone(), "An action row must contain a component"));
+        }
+
+        let width = self.components.iter().map(ActionRowComponent::width).sum::<usize>();
+        if width > limits::ACTION_ROW_WIDTH {
+            violations.push(violation(components_path.clone(), format!("An action row holds up to {} buttons, or a single select menu", limits::ACTION_ROW_WIDTH)));
+        }
+
+        for (idx, component) in self.components.iter().enumerate() {
+            let component_path = index_path(&components_path, idx);
+            match component {
+                ActionRowComponent::Button(button) => button.collect_violations(&component_path, violations),
+                ActionRowComponent::SelectMenu(select_menu) => select_menu.collect_violations(&component_path, violations)
+            }
+        }
+    }
+}
+
+impl Validate for Button {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.r#type != 2 {
+            violations.push(violation(field_path(path, "type"), format!("A button must have type 2, not {}", self.r#type)));
+        }
+
+        if self.label.is_none() && self.emoji.is_none() {
+            violations.push(violation(path.to_string(), "A button must have a label or an emoji"));
+        }
+
+        if let Some(label) = &self.label {
+            check_length(label, limits::BUTTON_LABEL_LENGTH, field_path(path, "label"), violations);
+        }
+
+        if let Some(custom_id) = &self.custom_id {
+            check_length(custom_id, limits::CUSTOM_ID_LENGTH, field_path(path, "custom_id"), violations);
+        }
+
+        if self.style == ButtonStyle::Link {
+            if self.url.is_none() {
+                violations.push(violation(field_path(path, "url"), "A link button must have a url"));
+            }
+
+            if self.custom_id.is_some() {
+                violations.push(violation(field_path(path, "custom_id"), "A link button cannot have a custom id"));
+            }
+
+            check_url_scheme(self.url.as_deref(), LINK_URL_SCHEMES, field_path(path, "url"), violations);
+        } else {
+            if self.custom_id.as_deref().map(str::is_empty).unwrap_or(true) {
+                violations.push(violation(field_path(path, "custom_id"), "A button which is not a link must have a custom id"));
+            }
+
+            if self.url.is_some() {
+                violations.push(violation(field_path(path, "url"), "Only link buttons can have a url"));
+            }
+        }
+    }
+}
+
+impl Validate for SelectMenu {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let custom_id_path = field_path(path, "custom_id");
+        if self.custom_id.is_empty() {
+            violations.push(violation(custom_id_path.clone(), "A select menu must have a custom id"));
+        }
+
+        check_length(&self.custom_id, limits::CUSTOM_ID_LENGTH, custom_id_path, violations);
+
+        if let Some(placeholder) = &self.placeholder {
+            check_length(placeholder, limits::SELECT_PLACEHOLDER_LENGTH, field_path(path, "placeholder"), violations);
+        }
+
+        let options_path = field_path(path, "options");
+        let options = self.options.as_deref().unwrap_or_default();
+        if self.r#type == SelectMenuType::String {
+            if options.is_empty() {
+                violations.push(violation(options_path.clone(), "A string select menu must have options"));
+            }
+
+            if options.len() > limits::SELECT_OPTIONS {
+                violations.push(violation(options_path.clone(), format!("There may be up to {} options", limits::SELECT_OPTIONS)));
+            }
+        } else if self.options.is_some() {
+            violations.push(violation(options_path.clone(), "Only string select menus can have options"));
+        }
+
+        for (idx, option) in options.iter().enumerate() {
+            option.collect_violations(&index_path(&options_path, idx), violations);
+        }
+
+        let max_allowed = if self.r#type == SelectMenuType::String {
+            options.len().min(limits::SELECT_OPTIONS)
+        } else {
+            limits::SELECT_OPTIONS
+        };
+
+        if let Some(min_values) = self.min_values {
+            if usize::from(min_values) > max_allowed {
+                violations.push(violation(field_path(path, "min_values"), format!("Must be {} or less", max_allowed)));
+            }
+        }
+
+        if let Some(max_values) = self.max_values {
+            if max_values == 0 || usize::from(max_values) > max_allowed {
+                violations.push(violation(field_path(path, "max_values"), format!("Must be between 1 and {}", max_allowed)));
+            }
+
+            if self.min_values.map(|min_values| min_values > max_values).unwrap_or_default() {
+                violations.push(violation(field_path(path, "min_values"), "May not exceed the maximum number of values"));
+            }
+        }
+    }
+}
+
+impl Validate for SelectOption {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (field, value) in [("label", &self.label), ("value", &self.value)].iter() {
+            let value_path = field_path(path, field);
+            if value.is_empty() {
+                violations.push(violation(value_path.clone(), "May not be empty"));
+            }
+
+            check_length(value, limits::SELECT_OPTION_LENGTH, value_path, violations);
+        }
+
+        if let Some(description) = &self.description {
+            check_length(description, limits::SELECT_OPTION_LENGTH, field_path(path, "description"), violations);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn component_payload() {
+        let row = ActionRowBuilder::new()
+            .add_button(ButtonBuilder::new().set_label("View logs").set_url("https://ci.example.com/logs/42").build())
+            .add_button(ButtonBuilder::new().set_style(ButtonStyle::Danger).set_label("Roll back").set_custom_id("rollback:42").set_emoji(ComponentEmoji::unicode("⏪")).build())
+            .build();
+
+        let select = SelectMenuBuilder::new()
+            .set_custom_id("environment")
+            .set_placeholder("Environment")
+            .add_option(SelectOptionBuilder::new().set_label("Staging").set_value("staging").set_default(true).build())
+            .add_option(SelectOptionBuilder::new().set_label("Production").set_value("production").build())
+            .set_max_values(2)
+            .build();
+
+        let webhook = WebhookBuilder::new()
+            .set_components(vec![row, ActionRowBuilder::new().add_select_menu(select).build()])
+            .build();
+
+        let payload = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(payload["components"], serde_json::json!([
+            {
+                "type": 1,
+                "components": [
+                    { "type": 2, "style": 5, "label": "View logs", "url": "https://ci.example.com/logs/42" },
+                    { "type": 2, "style": 4, "label": "Roll back", "emoji": { "name": "⏪" }, "custom_id": "rollback:42" }
+                ]
+            },
+            {
+                "type": 1,
+                "components": [{
+                    "type": 3,
+                    "custom_id": "environment",
+                    "options": [
+                        { "label": "Staging", "value": "staging", "default": true },
+                        { "label": "Production", "value": "production" }
+                    ],
+                    "placeholder": "Environment",
+                    "max_values": 2
+                }]
+            }
+        ]));
+    }
+
+    #[test]
+    fn component_types() {
+        let row: ActionRow = serde_json::from_str(r#"{"type": 1, "components": [{"type": 3, "style": 1, "label": "Deploy", "custom_id": "deploy"}]}"#).unwrap();
+        assert!(matches!(row.components[0], ActionRowComponent::SelectMenu(_)));
+        let err = WebhookBuilder::new().set_components(vec![row]).try_build().unwrap_err();
+        assert_eq!(err.violations[0].path, "components[0].components[0].options");
+
+        assert!(serde_json::from_str::<ActionRowComponent>(r#"{"type": 9, "custom_id": "deploy"}"#).is_err());
+        assert!(serde_json::from_str::<ActionRowComponent>(r#"{"style": 1, "custom_id": "deploy"}"#).is_err());
+
+        let button = ButtonBuilder::new().set_label("Deploy").set_custom_id("deploy").build();
+        let row = ActionRow { r#type: 4, components: vec![Button { r#type: 5, ..button }.into()] };
+        let err = WebhookBuilder::new().set_components(vec![row]).try_build().unwrap_err();
+        let paths = err.violations.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["components[0].type", "components[0].components[0].type"]);
+    }
+
+    #[test]
+    fn invalid_components() {
+        let link = ButtonBuilder::new().set_label("Open dashboard").set_url("https://dash.example.com").build();
+        let select = SelectMenuBuilder::new().set_custom_id("pick").add_option(SelectOptionBuilder::new().set_label("a").set_value("a").build()).build();
+
+        let err = ActionRowBuilder::new()
+            .set_components(vec![link.clone().into(), select.into()])
+            .try_build()
+            .unwrap_err();
+        assert_eq!(err.violations.len(), 1);
+        assert_eq!(err.violations[0].path, "components");
+
+        let rows = vec![ActionRowBuilder::new().add_button(link).build(); 6];
+        let err = WebhookBuilder::new().set_components(rows).try_build().unwrap_err();
+        assert_eq!(err.violations[0].path, "components");
+
+        let err = ButtonBuilder::new().set_style(ButtonStyle::Link).set_custom_id("a").try_build().unwrap_err();
+        let paths = err.violations.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["", "url", "custom_id"]);
+
+        let err = ButtonBuilder::new().set_label("Deploy").try_build().unwrap_err();
+        assert_eq!(err.violations[0].path, "custom_id");
+
+        let err = SelectMenuBuilder::new()
+            .set_custom_id("pick")
+            .add_option(SelectOptionBuilder::new().set_label("a").set_value("a").build())
+            .set_min_values(2)
+            .try_build()
+            .unwrap_err();
+        assert_eq!(err.violations[0].path, "min_values");
+
+        let button = ButtonBuilder::new().set_label("Retry").set_custom_id("retry").build();
+        let row = ActionRowBuilder::new().add_button(button.clone()).add_button(button).build();
+        let err = WebhookBuilder::new().set_components(vec![row]).try_build().unwrap_err();
+        assert_eq!(err.violations[0].path, "components[0].components[1].custom_id");
+    }
+}
@@ -1,15 +1,30 @@
 //! Structs related to the structure of a Discord Embed
 
-use serde::Serialize;
+use std::borrow::Cow;
+use serde::{Serialize, Deserialize};
+use crate::{Attachment, ValidationError, Violation};
+use crate::types::attachment::ATTACHMENT_URL_SCHEME;
+use crate::validation::{Validate, field_path, index_path, violation, check_url_scheme, check_length, unwrap_build};
+use crate::limits;
+use crate::truncation::{TruncationPolicy, budget_cap};
+
+/// The number of characters in `value`, as counted by Discord
+fn count_chars(value: &str) -> usize {
+    value.chars().count()
+}
+
+/// URL schemes supported for images and icons in an embed
+const IMAGE_URL_SCHEMES: &[&str] = &["http://", "https://", ATTACHMENT_URL_SCHEME];
 
 /// An Embed
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
 pub struct Embed<'a> {
-    /// title of embed
+    /// title of embed (up to 256 characters)
     pub title:          Option<String>,
     /// type of embed (always "rich" for webhook embeds)
-    pub r#type:         &'static str,
-    /// description of embed
+    pub r#type:         Cow<'static, str>,
+    /// description of embed (up to 4096 characters)
     pub description:    Option<String>,
     /// url of embed
     pub url:            Option<String>,
@@ -23,26 +38,104 @@ pub struct Embed<'a> {
     /// ```
     pub color:          Option<i64>,
     /// footer information
-    pub footer:         Option<&'a EmbedFooter>,
+    pub footer:         Option<Cow<'a, EmbedFooter>>,
     /// image information
-    pub image:          Option<&'a EmbedImage>,
+    pub image:          Option<Cow<'a, EmbedImage>>,
     /// thumbnail information
-    pub thumbnail:      Option<&'a EmbedThumbnail>,
+    pub thumbnail:      Option<Cow<'a, EmbedThumbnail>>,
     /// video information
-    pub video:          Option<&'a EmbedVideo>,
+    pub video:          Option<Cow<'a, EmbedVideo>>,
     /// provider information
-    pub provider:       Option<&'a EmbedProvider>,
+    pub provider:       Option<Cow<'a, EmbedProvider>>,
     /// author information
-    pub author:         Option<&'a EmbedAuthor>,
-    /// fields information
+    pub author:         Option<Cow<'a, EmbedAuthor>>,
+    /// fields information (up to 25 fields)
     pub fields:         Option<Vec<EmbedField>>
 }
 
+impl<'a> Embed<'a> {
+    /// The number of characters counting towards the limit of [limits::EMBEDS_TOTAL_LENGTH]:
+    /// the title, description, field names and values, footer text and author name
+    pub fn character_count(&self) -> usize {
+        self.text_lengths().iter().sum()
+    }
+
+    /// The lengths of every text counting towards the limit of [limits::EMBEDS_TOTAL_LENGTH]
+    pub(crate) fn text_lengths(&self) -> Vec<usize> {
+        let mut lengths = vec![
+            self.title.as_deref().map(count_chars).unwrap_or_default(),
+            self.description.as_deref().map(count_chars).unwrap_or_default(),
+            self.footer.as_ref().map(|footer| count_chars(&footer.text)).unwrap_or_default(),
+            self.author.as_ref().and_then(|author| author.name.as_deref()).map(count_chars).unwrap_or_default()
+        ];
+
+        for field in self.fields.iter().flatten() {
+            lengths.push(count_chars(&field.name));
+            lengths.push(count_chars(&field.value));
+        }
+
+        lengths
+    }
+
+    /// Shorten every text exceeding its limit, or exceeding `cap` characters
+    pub(crate) fn truncate(&mut self, policy: &TruncationPolicy, cap: usize) {
+        if let Some(title) = &mut self.title {
+            policy.truncate_string(title, limits::EMBED_TITLE_LENGTH.min(cap));
+        }
+
+        if let Some(description) = &mut self.description {
+            policy.truncate_string(description, limits::EMBED_DESCRIPTION_LENGTH.min(cap));
+        }
+
+        for field in self.fields.iter_mut().flatten() {
+            policy.truncate_string(&mut field.name, limits::EMBED_FIELD_NAME_LENGTH.min(cap));
+            policy.truncate_string(&mut field.value, limits::EMBED_FIELD_VALUE_LENGTH.min(cap));
+        }
+
+        if let Some(footer) = &mut self.footer {
+            if let Some(text) = policy.truncate(&footer.text, limits::EMBED_FOOTER_TEXT_LENGTH.min(cap)) {
+                footer.to_mut().text = text;
+            }
+        }
+
+        if let Some(author) = &mut self.author {
+            let name = author.name.as_deref().and_then(|name| policy.truncate(name, limits::EMBED_AUTHOR_NAME_LENGTH.min(cap)));
+            if name.is_some() {
+                author.to_mut().name = name;
+            }
+        }
+    }
+
+    /// Shorten every text exceeding its limit, and then the longest texts until the embed fits into a message on its own
+    pub(crate) fn truncate_to_fit(&mut self, policy: &TruncationPolicy) {
+        self.truncate(policy, usize::MAX);
+        if let Some(cap) = budget_cap(&self.text_lengths(), limits::EMBEDS_TOTAL_LENGTH) {
+            self.truncate(policy, cap);
+        }
+    }
+
+    /// The `attachment://` URLs this embed uses to refer to uploaded files
+    pub(crate) fn attachment_references(&self) -> Vec<&str> {
+        let urls = [
+            self.footer.as_ref().and_then(|footer| footer.icon_url.as_deref()),
+            self.image.as_ref().and_then(|image| image.url.as_deref()),
+            self.thumbnail.as_ref().and_then(|thumbnail| thumbnail.url.as_deref()),
+            self.author.as_ref().and_then(|author| author.icon_url.as_deref())
+        ];
+
+        urls.iter()
+            .flatten()
+            .filter(|url| url.starts_with(ATTACHMENT_URL_SCHEME))
+            .copied()
+            .collect()
+    }
+}
+
 impl<'a> Default for Embed<'a> {
     fn default() -> Self {
         Embed {
             title:          None,
-            r#type:         "rich",
+            r#type:         Cow::Borrowed("rich"),
             description:    None,
             url:            None,
             timestamp:      None,
@@ -62,13 +155,24 @@ impl<'a> Default for Embed<'a> {
 #[derive(Default)]
 pub struct EmbedBuilder<'a> {
     /// inner data
-    inner: Embed<'a>
+    inner: Embed<'a>,
+    /// requirements violated while setting values
+    violations: Vec<Violation>,
+    /// policy for shortening text exceeding Discord's limits
+    truncation: Option<TruncationPolicy>
 }
 
 impl<'a> EmbedBuilder<'a> {
     /// Create a new Builder
     pub fn new() -> Self {
-        Self { inner: Default::default() }
+        Self { inner: Default::default(), violations: Vec::new(), truncation: None }
+    }
+
+    /// shorten text exceeding Discord's limits according to `policy` when building, instead of rejecting it.
+    /// If the embed exceeds [limits::EMBEDS_TOTAL_LENGTH], its largest texts are shortened first
+    pub fn set_truncation(mut self, policy: TruncationPolicy) -> Self {
+        self.truncation = Some(policy);
+        self
     }
 
     /// title of embed
@@ -103,43 +207,47 @@ impl<'a> EmbedBuilder<'a> {
 
     /// color code of the embed in hexadecimal
     pub fn set_color_hex(mut self, color: &str) -> Self {
-        self.inner.color = Some(i64::from_str_radix(&color.replace("#", ""), 16).unwrap());
+        match i64::from_str_radix(&color.replace('#', ""), 16) {
+            Ok(color) => self.inner.color = Some(color),
+            Err(_) => self.violations.push(violation("color".to_string(), format!("'{}' is not a hexadecimal color", color)))
+        }
+
         self
     }
 
     /// footer information
     pub fn set_footer(mut self, footer: &'a EmbedFooter) -> Self {
-        self.inner.footer = Some(footer);
+        self.inner.footer = Some(Cow::Borrowed(footer));
         self
     }
 
     /// image information
     pub fn set_image(mut self, image: &'a EmbedImage) -> Self {
-        self.inner.image = Some(image);
+        self.inner.image = Some(Cow::Borrowed(image));
         self
     }
 
     /// thumbnail information
     pub fn set_thumbnail(mut self, thumbnail: &'a EmbedThumbnail) -> Self {
-        self.inner.thumbnail = Some(thumbnail);
+        self.inner.thumbnail = Some(Cow::Borrowed(thumbnail));
         self
     }
 
     /// video information
     pub fn set_video(mut self, video: &'a EmbedVideo) -> Self {
-        self.inner.video = Some(video);
+        self.inner.video = Some(Cow::Borrowed(video));
         self
     }
 
     /// provider information
     pub fn set_provider(mut self, provider: &'a EmbedProvider) -> Self {
-        self.inner.provider = Some(provider);
+        self.inner.provider = Some(Cow::Borrowed(provider));
         self
     }
 
     /// author information
     pub fn set_author(mut self, author: &'a EmbedAuthor) -> Self {
-        self.inner.author = Some(author);
+        self.inner.author = Some(Cow::Borrowed(author));
         self
     }
 
@@ -149,16 +257,40 @@ impl<'a> EmbedBuilder<'a> {
         self
     }
 
-    /// Build the Builder
+    /// Build the Embed
+    ///
+    /// # Errors
+    /// - If the color is not a valid hexadecimal color
+    /// - If any text exceeds Discord's limits, and no truncation policy has been set
+    /// - If any of the nested values is invalid
+    pub fn try_build(mut self) -> Result<Embed<'a>, ValidationError> {
+        if let Some(policy) = &self.truncation {
+            self.inner.truncate_to_fit(policy);
+        }
+
+        let mut violations = self.violations;
+        self.inner.collect_violations("", &mut violations);
+
+        if violations.is_empty() {
+            Ok(self.inner)
+        } else {
+            Err(ValidationError { violations })
+        }
+    }
+
+    /// Build the Embed
+    ///
+    /// # Panics
+    /// If the Embed is invalid, see [Self::try_build]
     pub fn build(self) -> Embed<'a> {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// The footer of an Embed
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedFooter {
-    /// footer text
+    /// footer text (up to 2048 characters)
     pub text:           String,
     /// url of footer icon (only supports http(s) and attachments)
     pub icon_url:       Option<String>,
@@ -173,38 +305,58 @@ pub struct EmbedFooterBuilder {
     inner: EmbedFooter
 }
 
-impl<'a> EmbedFooterBuilder {
+impl EmbedFooterBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// footer text
-    pub fn set_text<S: 'a + AsRef<str>>(mut self, text: S) -> Self {
+    pub fn set_text<S: AsRef<str>>(mut self, text: S) -> Self {
         self.inner.text = text.as_ref().to_string();
         self
     }
 
     /// url of footer icon (only supports http(s) and attachments)
-    pub fn set_icon_url<S: 'a + AsRef<str>>(mut self, icon_url: S) -> Self {
+    pub fn set_icon_url<S: AsRef<str>>(mut self, icon_url: S) -> Self {
         self.inner.icon_url = Some(icon_url.as_ref().to_string());
         self
     }
 
+    /// use an uploaded file as footer icon.
+    /// The attachment must be part of the same WebhookRequest
+    pub fn set_icon_attachment(mut self, attachment: &Attachment) -> Self {
+        self.inner.icon_url = Some(attachment.attachment_url());
+        self
+    }
+
     /// a proxied url of footer icon
-    pub fn set_proxy_icon_url<S: 'a + AsRef<str>>(mut self, proxy_icon_url: S) -> Self {
+    pub fn set_proxy_icon_url<S: AsRef<str>>(mut self, proxy_icon_url: S) -> Self {
         self.inner.proxy_icon_url = Some(proxy_icon_url.as_ref().to_string());
         self
     }
 
-    /// Build the Builder
+    /// Build the EmbedFooter
+    ///
+    /// # Errors
+    /// - If no text has been set
+    /// - If the icon url is not a http(s) or attachment url
+    pub fn try_build(self) -> Result<EmbedFooter, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedFooter
+    ///
+    /// # Panics
+    /// If the EmbedFooter is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedFooter {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed image
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedImage {
     /// source url of image (only supports http(s) and attachments)
     pub url:            Option<String>,
@@ -223,20 +375,27 @@ pub struct EmbedImageBuilder {
     inner: EmbedImage
 }
 
-impl<'a> EmbedImageBuilder {
+impl EmbedImageBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// source url of image (only supports http(s) and attachments)
-    pub fn set_url<S: 'a + AsRef<str>>(mut self, url: S) -> Self {
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
         self.inner.url = Some(url.as_ref().to_string());
         self
     }
 
+    /// use an uploaded file as image.
+    /// The attachment must be part of the same WebhookRequest
+    pub fn set_attachment(mut self, attachment: &Attachment) -> Self {
+        self.inner.url = Some(attachment.attachment_url());
+        self
+    }
+
     /// a proxied url of the image
-    pub fn set_proxy_url<S: 'a + AsRef<str>>(mut self, proxy_url: S) -> Self {
+    pub fn set_proxy_url<S: AsRef<str>>(mut self, proxy_url: S) -> Self {
         self.inner.proxy_url = Some(proxy_url.as_ref().to_string());
         self
     }
@@ -253,14 +412,26 @@ impl<'a> EmbedImageBuilder {
         self
     }
 
-    /// Build the Builder
+    /// Build the EmbedImage
+    ///
+    /// # Errors
+    /// - If the url is not a http(s) or attachment url
+    pub fn try_build(self) -> Result<EmbedImage, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedImage
+    ///
+    /// # Panics
+    /// If the EmbedImage is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedImage {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed Thumbnail
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedThumbnail {
     /// source url of thumbnail (only supports http(s) and attachments)
     pub url:            Option<String>,
@@ -279,20 +450,27 @@ pub struct EmbedThumbnailBuilder {
     inner: EmbedThumbnail
 }
 
-impl<'a> EmbedThumbnailBuilder {
+impl EmbedThumbnailBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// source url of thumbnail (only supports http(s) and attachments)
-    pub fn set_url<S: 'a + AsRef<str>>(mut self, url: S) -> Self {
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
         self.inner.url = Some(url.as_ref().to_string());
         self
     }
 
+    /// use an uploaded file as thumbnail.
+    /// The attachment must be part of the same WebhookRequest
+    pub fn set_attachment(mut self, attachment: &Attachment) -> Self {
+        self.inner.url = Some(attachment.attachment_url());
+        self
+    }
+
     /// a proxied url of the thumbnail
-    pub fn set_proxy_url<S: 'a + AsRef<str>>(mut self, proxy_url: S) -> Self {
+    pub fn set_proxy_url<S: AsRef<str>>(mut self, proxy_url: S) -> Self {
         self.inner.proxy_url = Some(proxy_url.as_ref().to_string());
         self
     }
@@ -309,14 +487,26 @@ impl<'a> EmbedThumbnailBuilder {
         self
     }
 
-    /// Build the Builder
+    /// Build the EmbedThumbnail
+    ///
+    /// # Errors
+    /// - If the url is not a http(s) or attachment url
+    pub fn try_build(self) -> Result<EmbedThumbnail, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedThumbnail
+    ///
+    /// # Panics
+    /// If the EmbedThumbnail is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedThumbnail {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed provider
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedProvider {
     /// name of provider
     pub name:           Option<String>,
@@ -331,34 +521,46 @@ pub struct EmbedProviderBuilder {
     inner: EmbedProvider
 }
 
-impl<'a> EmbedProviderBuilder {
+impl EmbedProviderBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// name of provider
-    pub fn set_name<S: 'a + AsRef<str>>(mut self, name: S) -> Self {
+    pub fn set_name<S: AsRef<str>>(mut self, name: S) -> Self {
         self.inner.name = Some(name.as_ref().to_string());
         self
     }
 
     /// url of provider
-    pub fn set_url<S: 'a + AsRef<str>>(mut self, url: S) -> Self {
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
         self.inner.url = Some(url.as_ref().to_string());
         self
     }
 
-    /// Build the Builder
+    /// Build the EmbedProvider
+    ///
+    /// # Errors
+    /// - Never, an EmbedProvider has no requirements to violate
+    pub fn try_build(self) -> Result<EmbedProvider, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedProvider
+    ///
+    /// # Panics
+    /// If the EmbedProvider is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedProvider {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed Author
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedAuthor {
-    /// name of author
+    /// name of author (up to 256 characters)
     pub name:           Option<String>,
     /// url of author
     pub url:            Option<String>,
@@ -375,44 +577,63 @@ pub struct EmbedAuthorBuilder {
     inner: EmbedAuthor
 }
 
-impl<'a> EmbedAuthorBuilder {
+impl EmbedAuthorBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// name of author
-    pub fn set_name<S: 'a + AsRef<str>>(mut self, name: S) -> Self {
+    pub fn set_name<S: AsRef<str>>(mut self, name: S) -> Self {
         self.inner.name = Some(name.as_ref().to_string());
         self
     }
 
     /// url of author
-    pub fn set_url<S: 'a + AsRef<str>>(mut self, url: S) -> Self {
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
         self.inner.url = Some(url.as_ref().to_string());
         self
     }
 
     /// url of author icon (only supports http(s) and attachments)
-    pub fn set_icon_url<S: 'a + AsRef<str>>(mut self, icon_url: S) -> Self {
+    pub fn set_icon_url<S: AsRef<str>>(mut self, icon_url: S) -> Self {
         self.inner.icon_url = Some(icon_url.as_ref().to_string());
         self
     }
 
+    /// use an uploaded file as author icon.
+    /// The attachment must be part of the same WebhookRequest
+    pub fn set_icon_attachment(mut self, attachment: &Attachment) -> Self {
+        self.inner.icon_url = Some(attachment.attachment_url());
+        self
+    }
+
     /// a proxied url of author icon
-    pub fn set_proxy_icon_url<S: 'a + AsRef<str>>(mut self, proxy_icon_url: S) -> Self {
+    pub fn set_proxy_icon_url<S: AsRef<str>>(mut self, proxy_icon_url: S) -> Self {
         self.inner.proxy_icon_url = Some(proxy_icon_url.as_ref().to_string());
         self
     }
 
-    /// Build the builder
+    /// Build the EmbedAuthor
+    ///
+    /// # Errors
+    /// - If the icon url is not a http(s) or attachment url
+    pub fn try_build(self) -> Result<EmbedAuthor, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedAuthor
+    ///
+    /// # Panics
+    /// If the EmbedAuthor is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedAuthor {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed Video
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedVideo {
     /// source url of video
     pub url:            Option<String>,
@@ -431,20 +652,20 @@ pub struct EmbedVideoBuilder {
     inner: EmbedVideo
 }
 
-impl<'a> EmbedVideoBuilder {
+impl EmbedVideoBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// source url of video
-    pub fn set_url<S: 'a + AsRef<str>>(mut self, url: S) -> Self {
+    pub fn set_url<S: AsRef<str>>(mut self, url: S) -> Self {
         self.inner.url = Some(url.as_ref().to_string());
         self
     }
 
     /// a proxied url of the video
-    pub fn set_proxy_url<S: 'a + AsRef<str>>(mut self, proxy_url: S) -> Self {
+    pub fn set_proxy_url<S: AsRef<str>>(mut self, proxy_url: S) -> Self {
         self.inner.proxy_url = Some(proxy_url.as_ref().to_string());
         self
     }
@@ -461,18 +682,30 @@ impl<'a> EmbedVideoBuilder {
         self
     }
 
-    /// Build the builder
+    /// Build the EmbedVideo
+    ///
+    /// # Errors
+    /// - Never, an EmbedVideo has no requirements to violate
+    pub fn try_build(self) -> Result<EmbedVideo, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedVideo
+    ///
+    /// # Panics
+    /// If the EmbedVideo is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedVideo {
-        self.inner
+        unwrap_build(self.try_build())
     }
 }
 
 /// Embed Field
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct EmbedField {
-    /// name of the field
+    /// name of the field (up to 256 characters)
     pub name:           String,
-    /// value of the field
+    /// value of the field (up to 1024 characters)
     pub value:          String,
     /// whether or not this field should display inline
     pub inline:         Option<bool>
@@ -485,20 +718,20 @@ pub struct EmbedFieldBuilder {
     inner: EmbedField
 }
 
-impl<'a> EmbedFieldBuilder {
+impl EmbedFieldBuilder {
     /// Create a new Builder
     pub fn new() -> Self {
         Self { inner: Default::default() }
     }
 
     /// name of the field
-    pub fn set_name<S: 'a + AsRef<str>>(mut self, name: S) -> Self {
+    pub fn set_name<S: AsRef<str>>(mut self, name: S) -> Self {
         self.inner.name = name.as_ref().to_string();
         self
     }
 
     /// value of the field
-    pub fn set_value<S: 'a + AsRef<str>>(mut self, value: S) -> Self {
+    pub fn set_value<S: AsRef<str>>(mut self, value: S) -> Self {
         self.inner.value = value.as_ref().to_string();
         self
     }
@@ -509,8 +742,164 @@ impl<'a> EmbedFieldBuilder {
         self
     }
 
-    /// Build the builder
+    /// Build the EmbedField
+    ///
+    /// # Errors
+    /// - If the name or value is empty
+    pub fn try_build(self) -> Result<EmbedField, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the EmbedField
+    ///
+    /// # Panics
+    /// If the EmbedField is invalid, see [Self::try_build]
     pub fn build(self) -> EmbedField {
-        self.inner
+        unwrap_build(self.try_build())
+    }
+}
+
+impl<'a> Validate for Embed<'a> {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(title) = &self.title {
+            check_length(title, limits::EMBED_TITLE_LENGTH, field_path(path, "title"), violations);
+        }
+
+        if let Some(description) = &self.description {
+            check_length(description, limits::EMBED_DESCRIPTION_LENGTH, field_path(path, "description"), violations);
+        }
+
+        if let Some(footer) = &self.footer {
+            footer.collect_violations(&field_path(path, "footer"), violations);
+        }
+
+        if let Some(image) = &self.image {
+            image.collect_violations(&field_path(path, "image"), violations);
+        }
+
+        if let Some(thumbnail) = &self.thumbnail {
+            thumbnail.collect_violations(&field_path(path, "thumbnail"), violations);
+        }
+
+        if let Some(author) = &self.author {
+            author.collect_violations(&field_path(path, "author"), violations);
+        }
+
+        if let Some(fields) = &self.fields {
+            let fields_path = field_path(path, "fields");
+            if fields.len() > limits::EMBED_FIELDS {
+                violations.push(violation(fields_path.clone(), format!("There may be up to {} fields", limits::EMBED_FIELDS)));
+            }
+
+            for (idx, field) in fields.iter().enumerate() {
+                field.collect_violations(&index_path(&fields_path, idx), violations);
+            }
+        }
+
+        let character_count = self.character_count();
+        if character_count > limits::EMBEDS_TOTAL_LENGTH {
+            violations.push(violation(path.to_string(), format!("The embed may contain up to {} characters, but contains {}", limits::EMBEDS_TOTAL_LENGTH, character_count)));
+        }
+    }
+}
+
+impl Validate for EmbedFooter {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.text.is_empty() {
+            violations.push(violation(field_path(path, "text"), "A footer must have text"));
+        }
+
+        check_length(&self.text, limits::EMBED_FOOTER_TEXT_LENGTH, field_path(path, "text"), violations);
+
+        check_url_scheme(self.icon_url.as_deref(), IMAGE_URL_SCHEMES, field_path(path, "icon_url"), violations);
+    }
+}
+
+impl Validate for EmbedImage {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_url_scheme(self.url.as_deref(), IMAGE_URL_SCHEMES, field_path(path, "url"), violations);
+    }
+}
+
+impl Validate for EmbedThumbnail {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_url_scheme(self.url.as_deref(), IMAGE_URL_SCHEMES, field_path(path, "url"), violations);
+    }
+}
+
+impl Validate for EmbedProvider {
+    fn collect_violations(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for EmbedAuthor {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(name) = &self.name {
+            check_length(name, limits::EMBED_AUTHOR_NAME_LENGTH, field_path(path, "name"), violations);
+        }
+
+        check_url_scheme(self.icon_url.as_deref(), IMAGE_URL_SCHEMES, field_path(path, "icon_url"), violations);
+    }
+}
+
+impl Validate for EmbedVideo {
+    fn collect_violations(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for EmbedField {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.name.is_empty() {
+            violations.push(violation(field_path(path, "name"), "A field must have a name"));
+        }
+
+        if self.value.is_empty() {
+            violations.push(violation(field_path(path, "value"), "A field must have a value"));
+        }
+
+        check_length(&self.name, limits::EMBED_FIELD_NAME_LENGTH, field_path(path, "name"), violations);
+        check_length(&self.value, limits::EMBED_FIELD_VALUE_LENGTH, field_path(path, "value"), violations);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn invalid_hex_color() {
+        let error = EmbedBuilder::new()
+            .set_color_hex("#notacolor")
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations[0].path, "color");
+    }
+
+    #[test]
+    fn embed_length_limits() {
+        let error = EmbedBuilder::new()
+            .set_title("a".repeat(257))
+            .set_description("é".repeat(4096))
+            .set_fields(vec![
+                EmbedField {
+                    name:   "Name".to_string(),
+                    value:  "a".repeat(1025),
+                    inline: None
+                };
+                26
+            ])
+            .try_build()
+            .unwrap_err();
+
+        let paths = error.violations.iter()
+            .map(|violation| violation.path.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(paths.contains(&"title"));
+        assert!(!paths.contains(&"description"));
+        assert!(paths.contains(&"fields"));
+        assert!(paths.contains(&"fields[25].value"));
+        assert!(paths.contains(&""));
     }
 }
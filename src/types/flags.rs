@@ -0,0 +1,209 @@
+//! Struct related to the flags of a message
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+/// Flags of a message, combined as a bitfield with `|`, e.g. `MessageFlags::SUPPRESS_EMBEDS | MessageFlags::SUPPRESS_NOTIFICATIONS`.
+/// Webhooks may only set the flags in [Self::WEBHOOK_ALLOWED], the other flags are set by Discord on received messages
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageFlags(u64);
+
+impl MessageFlags {
+    /// this message has been published to subscribed channels (via Channel Following)
+    pub const CROSSPOSTED: Self = Self(1 << 0);
+    /// this message originated from a message in another channel (via Channel Following)
+    pub const IS_CROSSPOST: Self = Self(1 << 1);
+    /// do not include any embeds when serializing this message
+    pub const SUPPRESS_EMBEDS: Self = Self(1 << 2);
+    /// the source message for this crosspost has been deleted (via Channel Following)
+    pub const SOURCE_MESSAGE_DELETED: Self = Self(1 << 3);
+    /// this message came from the urgent message system
+    pub const URGENT: Self = Self(1 << 4);
+    /// this message has an associated thread, with the same id as the message
+    pub const HAS_THREAD: Self = Self(1 << 5);
+    /// this message is only visible to the user who invoked the Interaction
+    pub const EPHEMERAL: Self = Self(1 << 6);
+    /// this message is an Interaction Response and the bot is "thinking"
+    pub const LOADING: Self = Self(1 << 7);
+    /// this message failed to mention some roles and add their members to the thread
+    pub const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD: Self = Self(1 << 8);
+    /// this message will not trigger push and desktop notifications
+    pub const SUPPRESS_NOTIFICATIONS: Self = Self(1 << 12);
+    /// this message is a voice message
+    pub const IS_VOICE_MESSAGE: Self = Self(1 << 13);
+    /// this message has a snapshot (via Message Forwarding)
+    pub const HAS_SNAPSHOT: Self = Self(1 << 14);
+    /// this message uses the layout components, instead of content and embeds
+    pub const IS_COMPONENTS_V2: Self = Self(1 << 15);
+
+    /// The flags a webhook may set when executing: SUPPRESS_EMBEDS and SUPPRESS_NOTIFICATIONS
+    pub const WEBHOOK_ALLOWED: Self = Self(Self::SUPPRESS_EMBEDS.0 | Self::SUPPRESS_NOTIFICATIONS.0);
+
+    /// Every named flag, used for debug printing
+    const NAMED: &'static [(&'static str, Self)] = &[
+        ("CROSSPOSTED", Self::CROSSPOSTED),
+        ("IS_CROSSPOST", Self::IS_CROSSPOST),
+        ("SUPPRESS_EMBEDS", Self::SUPPRESS_EMBEDS),
+        ("SOURCE_MESSAGE_DELETED", Self::SOURCE_MESSAGE_DELETED),
+        ("URGENT", Self::URGENT),
+        ("HAS_THREAD", Self::HAS_THREAD),
+        ("EPHEMERAL", Self::EPHEMERAL),
+        ("LOADING", Self::LOADING),
+        ("FAILED_TO_MENTION_SOME_ROLES_IN_THREAD", Self::FAILED_TO_MENTION_SOME_ROLES_IN_THREAD),
+        ("SUPPRESS_NOTIFICATIONS", Self::SUPPRESS_NOTIFICATIONS),
+        ("IS_VOICE_MESSAGE", Self::IS_VOICE_MESSAGE),
+        ("HAS_SNAPSHOT", Self::HAS_SNAPSHOT),
+        ("IS_COMPONENTS_V2", Self::IS_COMPONENTS_V2)
+    ];
+
+    /// No flags
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The flags with the given bits, including bits which are not known to this crate
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// The bits of the flags
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Whether no flag is set
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all of the given flags are set
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set the given flags
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Unset the given flags
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// The flags which are set, but which a webhook may not set
+    pub(crate) fn disallowed_for_webhooks(&self) -> Self {
+        Self(self.0 & !Self::WEBHOOK_ALLOWED.0)
+    }
+}
+
+impl BitOr for MessageFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for MessageFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+impl BitAnd for MessageFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// Lists the names of the flags, e.g. `SUPPRESS_EMBEDS | SUPPRESS_NOTIFICATIONS`, followed by the bits of unknown flags in hexadecimal
+impl fmt::Display for MessageFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = Self::NAMED.iter()
+            .filter(|(_, flag)| self.contains(*flag))
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+
+        let unknown = Self::NAMED.iter().fold(self.0, |bits, (_, flag)| bits & !flag.0);
+        if unknown != 0 {
+            names.push(format!("{:#x}", unknown));
+        }
+
+        if names.is_empty() {
+            write!(f, "(empty)")
+        } else {
+            write!(f, "{}", names.join(" | "))
+        }
+    }
+}
+
+impl fmt::Debug for MessageFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MessageFlags({})", self)
+    }
+}
+
+/// Serializes to the bits as a number, as Discord expects
+impl Serialize for MessageFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(u64::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn message_flags() {
+        let flags = MessageFlags::SUPPRESS_EMBEDS | MessageFlags::SUPPRESS_NOTIFICATIONS;
+        assert_eq!(flags.bits(), 4100);
+        assert!(flags.contains(MessageFlags::SUPPRESS_EMBEDS));
+        assert!(!flags.contains(MessageFlags::URGENT));
+        assert_eq!(flags.to_string(), "SUPPRESS_EMBEDS | SUPPRESS_NOTIFICATIONS");
+        assert_eq!(format!("{:?}", MessageFlags::from_bits(1 << 40)), "MessageFlags(0x10000000000)");
+
+        let webhook = WebhookBuilder::new()
+            .set_content("https://example.com")
+            .set_flags(flags)
+            .build();
+
+        assert_eq!(serde_json::to_value(&webhook).unwrap()["flags"], 4100);
+        assert!(serde_json::to_value(WebhookBuilder::new().set_content("Hello").build()).unwrap().get("flags").is_none());
+
+        let message: Message = serde_json::from_str(r#"{"id": "1", "channel_id": "2", "timestamp": "2021-10-01T12:00:00.000000+00:00", "flags": 4096}"#).unwrap();
+        assert_eq!(message.flags, MessageFlags::SUPPRESS_NOTIFICATIONS);
+    }
+
+    #[test]
+    fn invalid_message_flags() {
+        let err = WebhookBuilder::new()
+            .set_content("Hello")
+            .set_flags(MessageFlags::SUPPRESS_EMBEDS | MessageFlags::URGENT)
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(err.violations.len(), 1);
+        assert_eq!(err.violations[0].path, "flags");
+        assert!(err.violations[0].message.ends_with("not URGENT"));
+
+        // The layout components it requires are not supported, and Discord rejects it together with content
+        let err = WebhookBuilder::new()
+            .set_content("Hello")
+            .set_flags(MessageFlags::IS_COMPONENTS_V2)
+            .try_build()
+            .unwrap_err();
+        assert!(err.violations[0].message.ends_with("not IS_COMPONENTS_V2"));
+    }
+}
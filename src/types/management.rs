@@ -0,0 +1,196 @@
+//! Structs related to managing the webhook itself, rather than executing it
+
+use serde::{Serialize, Deserialize};
+use base64::Engine;
+use crate::{ValidationError, Violation};
+use crate::validation::{Validate, field_path, violation, check_length, unwrap_build};
+use crate::limits;
+
+/// A webhook, as returned by Discord
+#[derive(Deserialize, Clone, Debug)]
+pub struct WebhookInfo {
+    /// the id of the webhook
+    pub id:                 String,
+    /// the type of the webhook: 1 for incoming, 2 for channel follower and 3 for application webhooks
+    pub r#type:             u8,
+    /// the guild id this webhook is for, if any
+    pub guild_id:           Option<String>,
+    /// the channel id this webhook is for, if any
+    pub channel_id:         Option<String>,
+    /// the default name of the webhook
+    pub name:               Option<String>,
+    /// the default user avatar hash of the webhook
+    pub avatar:             Option<String>,
+    /// the bot/OAuth2 application that created this webhook
+    pub application_id:     Option<String>
+}
+
+impl WebhookInfo {
+    /// The URL of the default avatar of the webhook, if it has one.
+    /// Animated avatars, whose hash starts with `a_`, are a GIF, others a PNG
+    pub fn avatar_url(&self) -> Option<String> {
+        self.avatar.as_ref().map(|avatar| {
+            let extension = if avatar.starts_with("a_") { "gif" } else { "png" };
+            format!("https://cdn.discordapp.com/avatars/{}/{}.{}", self.id, avatar, extension)
+        })
+    }
+}
+
+/// Changes to the default name and avatar of a webhook
+#[derive(Default, Serialize, Clone, Debug)]
+pub struct WebhookModify {
+    /// the default name of the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name:               Option<String>,
+    /// image data URI for the default webhook avatar, or `Some(None)` to remove the avatar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar:             Option<Option<String>>
+}
+
+/// Builder for WebhookModify
+#[derive(Default)]
+pub struct WebhookModifyBuilder {
+    /// Inner data
+    inner: WebhookModify,
+    /// violations found while setting values
+    violations: Vec<Violation>
+}
+
+impl WebhookModifyBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default(), violations: Vec::new() }
+    }
+
+    /// the default name of the webhook (1-80 characters)
+    pub fn set_name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.inner.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// the default avatar of the webhook, from the raw bytes of a PNG, JPEG, GIF or WebP image
+    pub fn set_avatar<D: AsRef<[u8]>>(mut self, data: D) -> Self {
+        match image_data_uri(data.as_ref()) {
+            Some(data_uri) => self.inner.avatar = Some(Some(data_uri)),
+            None => self.violations.push(violation("avatar".to_string(), "Must be a PNG, JPEG, GIF or WebP image"))
+        }
+
+        self
+    }
+
+    /// remove the default avatar of the webhook
+    pub fn remove_avatar(mut self) -> Self {
+        self.inner.avatar = Some(None);
+        self
+    }
+
+    /// Build the WebhookModify
+    ///
+    /// # Errors
+    /// - If the name is empty, exceeds 80 characters or contains a reserved word
+    /// - If the avatar is not a supported image
+    pub fn try_build(mut self) -> Result<WebhookModify, ValidationError> {
+        self.inner.collect_violations("", &mut self.violations);
+        if !self.violations.is_empty() {
+            return Err(ValidationError { violations: self.violations });
+        }
+
+        Ok(self.inner)
+    }
+
+    /// Build the WebhookModify
+    ///
+    /// # Panics
+    /// If the WebhookModify is invalid, see [Self::try_build]
+    pub fn build(self) -> WebhookModify {
+        unwrap_build(self.try_build())
+    }
+}
+
+impl Validate for WebhookModify {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(name) = &self.name {
+            let name_path = field_path(path, "name");
+            if name.is_empty() {
+                violations.push(violation(name_path.clone(), "May not be empty"));
+            }
+
+            check_length(name, limits::WEBHOOK_NAME_LENGTH, name_path.clone(), violations);
+
+            let lowercase = name.to_lowercase();
+            for reserved in ["clyde", "discord"].iter() {
+                if lowercase.contains(reserved) {
+                    violations.push(violation(name_path.clone(), format!("May not contain '{}'", reserved)));
+                }
+            }
+        }
+    }
+}
+
+/// Encode an image as a data URI, e.g. `data:image/png;base64,...`.
+/// Returns `None` if the data is not a PNG, JPEG, GIF or WebP image
+fn image_data_uri(data: &[u8]) -> Option<String> {
+    let mime_type = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if data.starts_with(b"\xff\xd8\xff") {
+        "image/jpeg"
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        return None;
+    };
+
+    Some(format!("data:{};base64,{}", mime_type, base64::engine::general_purpose::STANDARD.encode(data)))
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn webhook_info_response() {
+        let body = r#"{"id": "1", "type": 1, "guild_id": "2", "channel_id": "3", "name": "Deploy bot", "avatar": "abc", "application_id": null, "token": "token"}"#;
+
+        let info: WebhookInfo = serde_json::from_str(body).unwrap();
+        assert_eq!(info.name.as_deref(), Some("Deploy bot"));
+        assert_eq!(info.channel_id.as_deref(), Some("3"));
+        assert_eq!(info.avatar_url().as_deref(), Some("https://cdn.discordapp.com/avatars/1/abc.png"));
+
+        let animated = WebhookInfo { avatar: Some("a_abc".to_string()), ..info.clone() };
+        assert_eq!(animated.avatar_url().as_deref(), Some("https://cdn.discordapp.com/avatars/1/a_abc.gif"));
+
+        let without_avatar = WebhookInfo { avatar: None, ..info };
+        assert!(without_avatar.avatar_url().is_none());
+    }
+
+    #[test]
+    fn modify_webhook_payload() {
+        let modify = WebhookModifyBuilder::new()
+            .set_name("Deploy bot")
+            .set_avatar(b"\x89PNG\r\n\x1a\nabc")
+            .build();
+
+        let payload = serde_json::to_value(&modify).unwrap();
+        assert_eq!(payload["name"], "Deploy bot");
+        assert_eq!(payload["avatar"], "data:image/png;base64,iVBORw0KGgphYmM=");
+
+        let removed = WebhookModifyBuilder::new().remove_avatar().build();
+        assert_eq!(serde_json::to_value(&removed).unwrap(), serde_json::json!({"avatar": null}));
+    }
+
+    #[test]
+    fn invalid_webhook_modify() {
+        let err = WebhookModifyBuilder::new()
+            .set_name("Discord bot")
+            .set_avatar("not an image")
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(err.violations.len(), 2);
+        assert_eq!(err.violations[0].path, "avatar");
+        assert_eq!(err.violations[1].path, "name");
+    }
+}
@@ -0,0 +1,78 @@
+//! Structs related to messages send by a webhook, as returned by Discord
+
+use serde::Deserialize;
+use crate::{Embed, MessageFlags};
+
+/// A message, as returned by Discord
+#[derive(Deserialize, Clone, Debug)]
+pub struct Message {
+    /// id of the message
+    pub id:                 String,
+    /// id of the channel the message was sent in
+    pub channel_id:         String,
+    /// id of the webhook that sent the message
+    pub webhook_id:         Option<String>,
+    /// contents of the message
+    #[serde(default)]
+    pub content:            String,
+    /// any embedded content
+    #[serde(default)]
+    pub embeds:             Vec<Embed<'static>>,
+    /// any attached files
+    #[serde(default)]
+    pub attachments:        Vec<MessageAttachment>,
+    /// when this message was sent, as an ISO8601 timestamp
+    pub timestamp:          String,
+    /// when this message was edited (or null if never), as an ISO8601 timestamp
+    pub edited_timestamp:   Option<String>,
+    /// message flags combined as a bitfield
+    #[serde(default)]
+    pub flags:              MessageFlags
+}
+
+/// A file attached to a message, as returned by Discord
+#[derive(Deserialize, Clone, Debug)]
+pub struct MessageAttachment {
+    /// attachment id
+    pub id:                 String,
+    /// name of file attached
+    pub filename:           String,
+    /// description for the file
+    pub description:        Option<String>,
+    /// the attachment's media type
+    pub content_type:       Option<String>,
+    /// size of file in bytes
+    pub size:               u64,
+    /// source url of file
+    pub url:                String,
+    /// a proxied url of file
+    pub proxy_url:          String,
+    /// height of file (if image)
+    pub height:             Option<u32>,
+    /// width of file (if image)
+    pub width:              Option<u32>
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn message_response() {
+        let body = r#"{"id": "1", "channel_id": "2", "webhook_id": "3", "content": "Hello", "embeds": [{"title": "Title", "type": "rich"}],
+            "attachments": [{"id": "4", "filename": "image.png", "size": 1024, "url": "https://cdn.discordapp.com/attachments/2/4/image.png",
+            "proxy_url": "https://media.discordapp.net/attachments/2/4/image.png", "content_type": "image/png", "width": 64, "height": 64}],
+            "timestamp": "2021-10-01T12:00:00.000000+00:00", "edited_timestamp": null, "tts": false}"#;
+
+        let message: Message = serde_json::from_str(body).unwrap();
+        assert_eq!(message.id, "1");
+        assert_eq!(message.channel_id, "2");
+        assert_eq!(message.content, "Hello");
+        assert_eq!(message.embeds.len(), 1);
+        assert_eq!(message.embeds[0].title.as_deref(), Some("Title"));
+        assert_eq!(message.attachments[0].url, "https://cdn.discordapp.com/attachments/2/4/image.png");
+        assert_eq!(message.attachments[0].width, Some(64));
+        assert!(message.edited_timestamp.is_none());
+    }
+}
@@ -3,5 +3,26 @@
 mod embed;
 pub use embed::*;
 
+mod attachment;
+pub use attachment::*;
+
 mod webhook;
-pub use webhook::*;
\ No newline at end of file
+pub use webhook::*;
+
+mod message;
+pub use message::*;
+
+mod management;
+pub use management::*;
+
+mod url;
+pub use url::*;
+
+mod flags;
+pub use flags::*;
+
+mod poll;
+pub use poll::*;
+
+mod component;
+pub use component::*;
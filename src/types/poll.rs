@@ -0,0 +1,324 @@
+//! Structs related to the structure of a Discord Poll
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error as _;
+use crate::{ValidationError, Violation};
+use crate::validation::{Validate, field_path, index_path, violation, check_length, unwrap_build};
+use crate::limits;
+
+/// A poll, attached to a message
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct Poll {
+    /// the question of the poll (only text is supported, up to 300 characters)
+    pub question:           PollMedia,
+    /// each of the answers available in the poll (up to 10)
+    pub answers:            Vec<PollAnswer>,
+    /// number of hours the poll should be open for (up to 32 days, defaults to 24 hours)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration:           Option<u32>,
+    /// whether a user can select multiple answers (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_multiselect:  Option<bool>,
+    /// the layout type of the poll (defaults to DEFAULT)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_type:        Option<PollLayoutType>
+}
+
+/// Builder for Poll
+#[derive(Default)]
+pub struct PollBuilder {
+    /// Inner data
+    inner: Poll
+}
+
+impl PollBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the question of the poll (up to 300 characters)
+    pub fn set_question<S: AsRef<str>>(mut self, question: S) -> Self {
+        self.inner.question = PollMedia { text: Some(question.as_ref().to_string()), emoji: None };
+        self
+    }
+
+    /// each of the answers available in the poll (up to 10)
+    pub fn set_answers(mut self, answers: Vec<PollAnswer>) -> Self {
+        self.inner.answers = answers;
+        self
+    }
+
+    /// add an answer to the poll
+    pub fn add_answer(mut self, answer: PollAnswer) -> Self {
+        self.inner.answers.push(answer);
+        self
+    }
+
+    /// number of hours the poll should be open for (up to 32 days)
+    pub fn set_duration(mut self, hours: u32) -> Self {
+        self.inner.duration = Some(hours);
+        self
+    }
+
+    /// whether a user can select multiple answers
+    pub fn set_allow_multiselect(mut self, allow_multiselect: bool) -> Self {
+        self.inner.allow_multiselect = Some(allow_multiselect);
+        self
+    }
+
+    /// the layout type of the poll
+    pub fn set_layout_type(mut self, layout_type: PollLayoutType) -> Self {
+        self.inner.layout_type = Some(layout_type);
+        self
+    }
+
+    /// Build the Poll
+    ///
+    /// # Errors
+    /// - If no question has been set, or the question exceeds 300 characters
+    /// - If there are no answers, or more than 10
+    /// - If any of the answers is invalid
+    /// - If the duration is not between 1 hour and 32 days
+    pub fn try_build(self) -> Result<Poll, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the Poll
+    ///
+    /// # Panics
+    /// If the Poll is invalid, see [Self::try_build]
+    pub fn build(self) -> Poll {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// The text and emoji of a poll's question or answer
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct PollMedia {
+    /// the text of the field (up to 300 characters for the question, and 55 for an answer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text:               Option<String>,
+    /// the emoji of the field, only supported for answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji:              Option<PollEmoji>
+}
+
+/// An answer of a poll
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct PollAnswer {
+    /// the data of the answer
+    pub poll_media:         PollMedia
+}
+
+/// Builder for PollAnswer
+#[derive(Default)]
+pub struct PollAnswerBuilder {
+    /// Inner data
+    inner: PollAnswer
+}
+
+impl PollAnswerBuilder {
+    /// Create a new Builder
+    pub fn new() -> Self {
+        Self { inner: Default::default() }
+    }
+
+    /// the text of the answer (up to 55 characters)
+    pub fn set_text<S: AsRef<str>>(mut self, text: S) -> Self {
+        self.inner.poll_media.text = Some(text.as_ref().to_string());
+        self
+    }
+
+    /// the emoji shown with the answer
+    pub fn set_emoji(mut self, emoji: PollEmoji) -> Self {
+        self.inner.poll_media.emoji = Some(emoji);
+        self
+    }
+
+    /// Build the PollAnswer
+    ///
+    /// # Errors
+    /// - If no text has been set, or the text exceeds 55 characters
+    /// - If the emoji is invalid
+    pub fn try_build(self) -> Result<PollAnswer, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the PollAnswer
+    ///
+    /// # Panics
+    /// If the PollAnswer is invalid, see [Self::try_build]
+    pub fn build(self) -> PollAnswer {
+        unwrap_build(self.try_build())
+    }
+}
+
+/// The emoji of a poll answer
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum PollEmoji {
+    /// a custom emoji of a guild
+    Custom {
+        /// the id of the emoji
+        id: String
+    },
+    /// a unicode emoji, e.g. "🚀"
+    Unicode {
+        /// the emoji itself
+        name: String
+    }
+}
+
+impl PollEmoji {
+    /// The custom emoji with the given id
+    pub fn custom<S: AsRef<str>>(id: S) -> Self {
+        Self::Custom { id: id.as_ref().to_string() }
+    }
+
+    /// The given unicode emoji
+    pub fn unicode<S: AsRef<str>>(emoji: S) -> Self {
+        Self::Unicode { name: emoji.as_ref().to_string() }
+    }
+}
+
+/// The layout of a poll
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PollLayoutType {
+    /// the default layout type
+    Default
+}
+
+impl Serialize for PollLayoutType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Default => serializer.serialize_u8(1)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PollLayoutType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Self::Default),
+            layout_type => Err(D::Error::custom(format!("unsupported poll layout type {}", layout_type)))
+        }
+    }
+}
+
+impl Validate for Poll {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let question_path = field_path(&field_path(path, "question"), "text");
+        match self.question.text.as_deref() {
+            Some(question) if !question.is_empty() => check_length(question, limits::POLL_QUESTION_LENGTH, question_path, violations),
+            _ => violations.push(violation(question_path, "A poll must have a question"))
+        }
+
+        if self.question.emoji.is_some() {
+            violations.push(violation(field_path(&field_path(path, "question"), "emoji"), "The question of a poll cannot have an emoji"));
+        }
+
+        let answers_path = field_path(path, "answers");
+        if self.answers.is_empty() {
+            violations.push(violation(answers_path.clone(), "A poll must have at least one answer"));
+        }
+
+        if self.answers.len() > limits::POLL_ANSWERS {
+            violations.push(violation(answers_path.clone(), format!("There may be up to {} answers", limits::POLL_ANSWERS)));
+        }
+
+        for (idx, answer) in self.answers.iter().enumerate() {
+            answer.collect_violations(&index_path(&answers_path, idx), violations);
+        }
+
+        if let Some(duration) = self.duration {
+            if duration == 0 || duration > limits::POLL_DURATION_HOURS {
+                violations.push(violation(field_path(path, "duration"), format!("Must be between 1 and {} hours", limits::POLL_DURATION_HOURS)));
+            }
+        }
+    }
+}
+
+impl Validate for PollAnswer {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let media_path = field_path(path, "poll_media");
+        let text_path = field_path(&media_path, "text");
+        match self.poll_media.text.as_deref() {
+            Some(text) if !text.is_empty() => check_length(text, limits::POLL_ANSWER_LENGTH, text_path, violations),
+            _ => violations.push(violation(text_path, "An answer must have text"))
+        }
+
+        if let Some(emoji) = &self.poll_media.emoji {
+            let emoji_path = field_path(&media_path, "emoji");
+            match emoji {
+                PollEmoji::Custom { id } if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) => {
+                    violations.push(violation(emoji_path, "The id of a custom emoji must be a number"));
+                },
+                PollEmoji::Unicode { name } if name.is_empty() => {
+                    violations.push(violation(emoji_path, "The emoji may not be empty"));
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn poll_payload() {
+        let poll = PollBuilder::new()
+            .set_question("Which release window?")
+            .add_answer(PollAnswerBuilder::new().set_text("Tuesday").set_emoji(PollEmoji::unicode("🚀")).build())
+            .add_answer(PollAnswerBuilder::new().set_text("Thursday").set_emoji(PollEmoji::custom("123")).build())
+            .set_duration(48)
+            .set_allow_multiselect(true)
+            .set_layout_type(PollLayoutType::Default)
+            .build();
+
+        let webhook = WebhookBuilder::new().set_poll(poll).build();
+        let payload = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(payload["poll"], serde_json::json!({
+            "question": { "text": "Which release window?" },
+            "answers": [
+                { "poll_media": { "text": "Tuesday", "emoji": { "name": "🚀" } } },
+                { "poll_media": { "text": "Thursday", "emoji": { "id": "123" } } }
+            ],
+            "duration": 48,
+            "allow_multiselect": true,
+            "layout_type": 1
+        }));
+    }
+
+    #[test]
+    fn invalid_poll() {
+        let err = PollBuilder::new()
+            .set_answers(vec![PollAnswer::default(); 11])
+            .set_duration(769)
+            .try_build()
+            .unwrap_err();
+
+        let paths = err.violations.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths[..2], ["question.text", "answers"]);
+        assert!(paths.contains(&"answers[10].poll_media.text"));
+        assert_eq!(paths.last(), Some(&"duration"));
+
+        let err = PollBuilder::new()
+            .set_question("q".repeat(301))
+            .add_answer(PollAnswer { poll_media: PollMedia { text: Some("a".repeat(56)), emoji: Some(PollEmoji::custom("rocket")) } })
+            .try_build()
+            .unwrap_err();
+
+        let paths = err.violations.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["question.text", "answers[0].poll_media.text", "answers[0].poll_media.emoji"]);
+
+        let poll = PollBuilder::new().set_question("Lunch?").add_answer(PollAnswerBuilder::new().set_text("Yes").build()).build();
+        let req = WebhookRequestBuilder::new().set_data(WebhookBuilder::new().set_poll(poll).build()).build();
+        assert_eq!(req.validate_edit().unwrap_err().violations[0].path, "poll");
+    }
+}
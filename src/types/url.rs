@@ -0,0 +1,348 @@
+//! Struct related to the URL of a Discord webhook
+
+use std::fmt;
+use std::str::FromStr;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use crate::UrlError;
+
+/// Hosts Discord serves its API on
+const HOSTS: &[&str] = &[
+    "discord.com",
+    "canary.discord.com",
+    "ptb.discord.com",
+    "discordapp.com",
+    "canary.discordapp.com",
+    "ptb.discordapp.com"
+];
+
+/// Placeholder shown instead of a webhook's token
+const REDACTED: &str = "****";
+
+/// The URL of Discord's API, which requests are send to unless the client is configured otherwise
+pub(crate) const DEFAULT_API_URL: &str = "https://discord.com/api";
+
+/// The URL of a webhook, identified by its id and token.
+/// Parses URLs like `https://discord.com/api/webhooks/{id}/{token}`, including the `discordapp.com` domain,
+/// the `canary` and `ptb` subdomains and a versioned API path like `/api/v10/webhooks/...`.
+/// Requests are send to `https://discord.com/api`, unless the client is configured with another base URL or API version,
+/// see [crate::WebhookClientBuilder::set_base_url].
+///
+/// Messages are send to, edited in, fetched from and deleted from the thread set with [Self::with_thread_id],
+/// or with a `thread_id` query parameter in the parsed URL. Other query parameters of a parsed URL, like `wait`, are ignored,
+/// as they are set by the client for each request.
+///
+/// The token is a secret, so it is redacted when the URL is displayed or debug printed,
+/// e.g. `https://discord.com/api/webhooks/123/****`. Use [Self::reveal] to get the full URL
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct WebhookUrl {
+    /// the id of the webhook
+    id:         String,
+    /// the secure token of the webhook
+    token:      String,
+    /// the id of the thread within the webhook's channel to target
+    thread_id:  Option<String>
+}
+
+impl WebhookUrl {
+    /// Create the URL of the webhook with the given `id` and `token`
+    ///
+    /// # Errors
+    /// - If the id is not a snowflake
+    /// - If the token is empty or contains characters other than letters, digits, `-` and `_`
+    pub fn new<A: AsRef<str>, B: AsRef<str>>(id: A, token: B) -> Result<Self, UrlError> {
+        let (id, token) = (id.as_ref(), token.as_ref());
+
+        if !is_snowflake(id) {
+            return Err(url_error("The webhook id must be a number"));
+        }
+
+        if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(url_error("The webhook token may only contain letters, digits, '-' and '_'"));
+        }
+
+        Ok(Self {
+            id:         id.to_string(),
+            token:      token.to_string(),
+            thread_id:  None
+        })
+    }
+
+    /// Target the thread with the given id, within the webhook's channel.
+    /// The thread is automatically unarchived when a message is send to it
+    ///
+    /// # Errors
+    /// - If the thread id is not a snowflake
+    pub fn with_thread_id<S: AsRef<str>>(mut self, thread_id: S) -> Result<Self, UrlError> {
+        let thread_id = thread_id.as_ref();
+        if !is_snowflake(thread_id) {
+            return Err(url_error("The thread id must be a number"));
+        }
+
+        self.thread_id = Some(thread_id.to_string());
+        Ok(self)
+    }
+
+    /// the id of the webhook
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// the secure token of the webhook
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// the id of the targeted thread, if any
+    pub fn thread_id(&self) -> Option<&str> {
+        self.thread_id.as_deref()
+    }
+
+    /// The full URL of the webhook, including its token
+    pub fn reveal(&self) -> String {
+        self.request_url(DEFAULT_API_URL)
+    }
+
+    /// The URL messages of the webhook are send to, on the API at `api_url`, e.g. `https://discord.com/api`
+    pub(crate) fn request_url(&self, api_url: &str) -> String {
+        self.with_query(self.webhook_url(api_url))
+    }
+
+    /// The URL of the webhook itself, without the targeted thread, on the API at `api_url`
+    pub(crate) fn webhook_url(&self, api_url: &str) -> String {
+        format!("{}/webhooks/{}/{}", api_url, self.id, self.token)
+    }
+
+    /// The URL of the message with the given id, send by the webhook, on the API at `api_url`
+    pub(crate) fn message_url(&self, api_url: &str, message_id: &str) -> String {
+        self.with_query(format!("{}/webhooks/{}/{}/messages/{}", api_url, self.id, self.token, message_id))
+    }
+
+    /// Append the id of the targeted thread to `url`, if there is one
+    fn with_query(&self, url: String) -> String {
+        match &self.thread_id {
+            Some(thread_id) => format!("{}?thread_id={}", url, thread_id),
+            None => url
+        }
+    }
+}
+
+impl FromStr for WebhookUrl {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let url = reqwest::Url::parse(s.trim())
+            .map_err(|e| url_error(format!("Not a URL: {}", e)))?;
+
+        if url.scheme() != "https" {
+            return Err(url_error("The URL must use https"));
+        }
+
+        let host = url.host_str().unwrap_or_default();
+        if !HOSTS.contains(&host) {
+            return Err(url_error(format!("'{}' is not a Discord domain", host)));
+        }
+
+        let segments = url.path_segments()
+            .map(|segments| segments.filter(|segment| !segment.is_empty()).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let path = match segments.as_slice() {
+            ["api", version, path @ ..] if is_api_version(version) => path,
+            ["api", path @ ..] => path,
+            _ => &[]
+        };
+
+        match path {
+            ["webhooks", id, token] => {
+                let mut webhook_url = Self::new(id, token)?;
+                for (name, value) in url.query_pairs() {
+                    if name == "thread_id" {
+                        webhook_url = webhook_url.with_thread_id(value)?;
+                    }
+                }
+
+                Ok(webhook_url)
+            },
+            _ => Err(url_error("The path must be /api/webhooks/{id}/{token}"))
+        }
+    }
+}
+
+impl fmt::Display for WebhookUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.with_query(format!("{}/webhooks/{}/{}", DEFAULT_API_URL, self.id, REDACTED)))
+    }
+}
+
+impl fmt::Debug for WebhookUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookUrl")
+            .field("id", &self.id)
+            .field("token", &REDACTED)
+            .field("thread_id", &self.thread_id)
+            .finish()
+    }
+}
+
+/// Serializes to the full URL, including the token
+impl Serialize for WebhookUrl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.reveal())
+    }
+}
+
+impl<'de> Deserialize<'de> for WebhookUrl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let url = String::deserialize(deserializer)?;
+        url.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A value which can be used as the URL of a webhook, such as a [WebhookUrl] or a string containing one
+pub trait IntoWebhookUrl {
+    /// Turn the value into a [WebhookUrl]
+    ///
+    /// # Errors
+    /// - If the value is not a valid webhook URL
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError>;
+}
+
+impl IntoWebhookUrl for WebhookUrl {
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError> {
+        Ok(self)
+    }
+}
+
+impl IntoWebhookUrl for &WebhookUrl {
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoWebhookUrl for &str {
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError> {
+        self.parse()
+    }
+}
+
+impl IntoWebhookUrl for String {
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError> {
+        self.parse()
+    }
+}
+
+impl IntoWebhookUrl for &String {
+    fn into_webhook_url(self) -> Result<WebhookUrl, UrlError> {
+        self.parse()
+    }
+}
+
+/// Replace the token in the path of a webhook URL, e.g. `/api/webhooks/{id}/{token}/messages/{message_id}`, with a placeholder
+pub(crate) fn redact_url(url: &mut reqwest::Url) {
+    let mut segments = match url.path_segments() {
+        Some(segments) => segments.map(str::to_string).collect::<Vec<_>>(),
+        None => return
+    };
+
+    let token_idx = match segments.iter().position(|segment| segment == "webhooks") {
+        Some(idx) if idx + 2 < segments.len() => idx + 2,
+        _ => return
+    };
+
+    segments[token_idx] = REDACTED.to_string();
+    url.set_path(&segments.join("/"));
+}
+
+/// Whether `id` is a snowflake, Discord's numeric ids
+fn is_snowflake(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Whether `segment` is an API version, like `v10`
+fn is_api_version(segment: &str) -> bool {
+    segment.len() > 1 && segment.starts_with('v') && segment[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Create an error describing what is wrong with a URL
+fn url_error<S: AsRef<str>>(message: S) -> UrlError {
+    UrlError { message: message.as_ref().to_string() }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn parse_webhook_urls() {
+        let urls = [
+            "https://discord.com/api/webhooks/123/abc-DEF_1",
+            "https://discordapp.com/api/webhooks/123/abc-DEF_1",
+            "https://canary.discord.com/api/webhooks/123/abc-DEF_1/",
+            "https://ptb.discordapp.com/api/v10/webhooks/123/abc-DEF_1",
+        ];
+
+        for url in urls.iter() {
+            let webhook_url: WebhookUrl = url.parse().unwrap();
+            assert_eq!(webhook_url.id(), "123");
+            assert_eq!(webhook_url.token(), "abc-DEF_1");
+            assert_eq!(webhook_url.reveal(), "https://discord.com/api/webhooks/123/abc-DEF_1");
+        }
+
+        let with_query: WebhookUrl = "https://discord.com/api/webhooks/123/abc?thread_id=456".parse().unwrap();
+        assert_eq!(with_query.reveal(), "https://discord.com/api/webhooks/123/abc?thread_id=456");
+
+        // The client sets `wait` and `with_components` for each request, other parameters are not supported
+        let with_query: WebhookUrl = "https://discord.com/api/webhooks/123/abc?wait=false&with_components=true&foo=bar&thread_id=456".parse().unwrap();
+        assert_eq!(with_query.reveal(), "https://discord.com/api/webhooks/123/abc?thread_id=456");
+        assert_eq!(with_query.webhook_url("https://discord.com/api"), "https://discord.com/api/webhooks/123/abc");
+    }
+
+    #[test]
+    fn invalid_webhook_urls() {
+        let urls = [
+            "not a url",
+            "http://discord.com/api/webhooks/123/abc",
+            "https://example.com/api/webhooks/123/abc",
+            "https://discord.com/api/webhooks/123",
+            "https://discord.com/api/webhooks/abc/abc",
+            "https://discord.com/api/v10/webhooks/123/abc/extra",
+        ];
+
+        for url in urls.iter() {
+            assert!(url.parse::<WebhookUrl>().is_err(), "{} should be invalid", url);
+        }
+
+        assert!(WebhookUrl::new("123", "a/b").is_err());
+    }
+
+    #[test]
+    fn webhook_url_serde() {
+        let url: WebhookUrl = serde_json::from_str(r#""https://discordapp.com/api/webhooks/123/abc""#).unwrap();
+        assert_eq!(url, WebhookUrl::new("123", "abc").unwrap());
+        assert_eq!(serde_json::to_string(&url).unwrap(), r#""https://discord.com/api/webhooks/123/abc""#);
+        assert!(serde_json::from_str::<WebhookUrl>(r#""https://example.com""#).is_err());
+    }
+
+    #[test]
+    fn webhook_url_redacts_token() {
+        let url = WebhookUrl::new("123", "secret").unwrap();
+        assert_eq!(url.to_string(), "https://discord.com/api/webhooks/123/****");
+        assert!(!format!("{:?}", url).contains("secret"));
+        assert_eq!(url.token(), "secret");
+    }
+
+    #[test]
+    fn thread_urls() {
+        let url: WebhookUrl = "https://discord.com/api/webhooks/123/abc?foo=bar&thread_id=456".parse().unwrap();
+        assert_eq!(url.thread_id(), Some("456"));
+        assert_eq!(url.reveal(), "https://discord.com/api/webhooks/123/abc?thread_id=456");
+
+        let url = WebhookUrl::new("123", "abc").unwrap().with_thread_id("789").unwrap();
+        assert_eq!(url.reveal(), "https://discord.com/api/webhooks/123/abc?thread_id=789");
+        assert_eq!(url.to_string(), "https://discord.com/api/webhooks/123/****?thread_id=789");
+
+        assert!(WebhookUrl::new("123", "abc").unwrap().with_thread_id("general").is_err());
+        assert!("https://discord.com/api/webhooks/123/abc?thread_id=".parse::<WebhookUrl>().is_err());
+    }
+}
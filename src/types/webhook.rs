@@ -1,55 +1,94 @@
 //! Struct related to the structure of a Discord Webhook
 
-use serde::Serialize;
-use crate::Embed;
+use std::borrow::Cow;
+use serde::{Serialize, Deserialize};
+use crate::{Embed, Attachment, MessageFlags, Poll, ActionRow, ValidationError, Violation};
+use crate::types::component::collect_component_violations;
+use crate::types::attachment::serialize_attachments;
+use crate::validation::{Validate, field_path, index_path, violation, check_length, unwrap_build};
+use crate::limits;
+use crate::truncation::{TruncationPolicy, budget_cap};
 
 /// A webhook
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct Webhook<'a> {
     /// the message contents (up to 2000 characters)
-    pub content:            Option<&'a str>,
+    pub content:            Option<Cow<'a, str>>,
     /// override the default username of the webhook
-    pub username:           Option<&'a str>,
+    pub username:           Option<Cow<'a, str>>,
     /// override the default avatar of the webhook
-    pub avatar_url:         Option<&'a str>,
+    pub avatar_url:         Option<Cow<'a, str>>,
     /// true if this is a TTS message
     pub tts:                Option<bool>,
-    /// the contents of the file being sent
-    pub file:               Option<&'a [u8]>,
     /// embedded rich content
     pub embeds:             Option<Vec<Embed<'a>>>,
     /// allowed mentions for the message
-    pub allowed_mentions:   Option<&'a AllowedMention<'a>>,
+    pub allowed_mentions:   Option<Cow<'a, AllowedMention<'a>>>,
+    /// the files being sent.
+    /// The file contents are uploaded as `multipart/form-data` parts, the payload only contains their metadata,
+    /// so attachments are not deserialized
+    #[serde(serialize_with = "serialize_attachments", skip_deserializing)]
+    pub attachments:        Option<Vec<Attachment>>,
+    /// a poll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll:               Option<Poll>,
+    /// rows of buttons and select menus (up to 5).
+    /// Webhooks which are not owned by an application can only send link buttons
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components:         Option<Vec<ActionRow>>,
+    /// message flags combined as a bitfield, only those in [MessageFlags::WEBHOOK_ALLOWED] can be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags:              Option<MessageFlags>,
+    /// name of the thread to create, if the webhook's channel is a forum or media channel (up to 100 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_name:        Option<Cow<'a, str>>,
+    /// ids of the tags to apply to the created forum post (up to 5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_tags:       Option<Vec<Cow<'a, str>>>,
+    /// whether content and embeds exceeding the limits may be split into multiple messages.
+    /// This is not part of the payload, see [crate::WebhookRequest::execute_url_split]
+    #[serde(skip)]
+    pub split:              bool,
 }
 
 /// Builder for Webhook
 #[derive(Default)]
 pub struct WebhookBuilder<'a> {
     /// Inner data
-    inner: Webhook<'a>
+    inner: Webhook<'a>,
+    /// policy for shortening text exceeding Discord's limits
+    truncation: Option<TruncationPolicy>
 }
 
 impl<'a> WebhookBuilder<'a> {
     /// Create a new Builder
     pub fn new() -> Self {
-        Self { inner: Default::default() }
+        Self { inner: Default::default(), truncation: None }
+    }
+
+    /// shorten text exceeding Discord's limits according to `policy` when building, instead of rejecting it.
+    /// This applies to the content, attachment descriptions and the text of all embeds.
+    /// If the embeds exceed [limits::EMBEDS_TOTAL_LENGTH] combined, their largest texts are shortened first
+    pub fn set_truncation(mut self, policy: TruncationPolicy) -> Self {
+        self.truncation = Some(policy);
+        self
     }
 
     /// the message contents (up to 2000 characters)
     pub fn set_content(mut self, content: &'a str) -> Self {
-        self.inner.content = Some(content);
+        self.inner.content = Some(Cow::Borrowed(content));
         self
     }
 
     /// override the default username of the webhook
     pub fn set_username(mut self, username: &'a str) -> Self {
-        self.inner.username = Some(username);
+        self.inner.username = Some(Cow::Borrowed(username));
         self
     }
 
     /// override the default avatar of the webhook
     pub fn set_avatar_url(mut self, avatar_url: &'a str) -> Self {
-        self.inner.avatar_url = Some(avatar_url);
+        self.inner.avatar_url = Some(Cow::Borrowed(avatar_url));
         self
     }
 
@@ -59,12 +98,6 @@ impl<'a> WebhookBuilder<'a> {
         self
     }
 
-    /// the contents of the file being sent
-    pub fn set_file(mut self, file: &'a [u8]) -> Self {
-        self.inner.file = Some(file);
-        self
-    }
-
     /// embedded rich content
     pub fn set_embeds(mut self, embeds: Vec<Embed<'a>>) -> Self {
         self.inner.embeds = Some(embeds);
@@ -73,49 +106,273 @@ impl<'a> WebhookBuilder<'a> {
 
     /// allowed mentions for the message
     pub fn set_allowed_mentions(mut self, allowed_mentions: &'a AllowedMention<'a>) -> Self {
-        self.inner.allowed_mentions = Some(allowed_mentions);
+        self.inner.allowed_mentions = Some(Cow::Borrowed(allowed_mentions));
+        self
+    }
+
+    /// the files being sent
+    pub fn set_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        self.inner.attachments = Some(attachments);
+        self
+    }
+
+    /// a poll, see [crate::PollBuilder]
+    pub fn set_poll(mut self, poll: Poll) -> Self {
+        self.inner.poll = Some(poll);
+        self
+    }
+
+    /// rows of buttons and select menus (up to 5), see [crate::ActionRowBuilder]
+    pub fn set_components(mut self, components: Vec<ActionRow>) -> Self {
+        self.inner.components = Some(components);
+        self
+    }
+
+    /// message flags combined as a bitfield, e.g. `MessageFlags::SUPPRESS_EMBEDS | MessageFlags::SUPPRESS_NOTIFICATIONS`.
+    /// Only those in [MessageFlags::WEBHOOK_ALLOWED] can be set
+    pub fn set_flags(mut self, flags: MessageFlags) -> Self {
+        self.inner.flags = Some(flags);
+        self
+    }
+
+    /// name of the thread to create, if the webhook's channel is a forum or media channel (up to 100 characters).
+    /// Cannot be combined with a thread id in the webhook URL, see [crate::WebhookUrl::with_thread_id]
+    pub fn set_thread_name(mut self, thread_name: &'a str) -> Self {
+        self.inner.thread_name = Some(Cow::Borrowed(thread_name));
+        self
+    }
+
+    /// ids of the tags to apply to the created forum post (up to 5). Requires [Self::set_thread_name]
+    pub fn set_applied_tags(mut self, applied_tags: Vec<&'a str>) -> Self {
+        self.inner.applied_tags = Some(applied_tags.into_iter().map(Cow::Borrowed).collect());
+        self
+    }
+
+    /// allow content exceeding the limit and any number of embeds, to be split into multiple messages when executing the webhook
+    /// with [crate::WebhookRequest::execute_url_split] or [crate::WebhookRequest::execute_split]
+    pub fn set_split(mut self, split: bool) -> Self {
+        self.inner.split = split;
         self
     }
 
+    /// Build the Webhook
+    ///
+    /// # Errors
+    /// - If none of `content`, `embeds`, `attachments`, `poll` or `components` has been set
+    /// - If there are more than 10 embeds, and splitting is not allowed
+    /// - If any text exceeds Discord's limits, and no truncation policy has been set
+    /// - If the thread name is empty, or tags are applied without a thread name or exceed 5
+    /// - If a flag is set which webhooks may not set
+    /// - If any of the embeds, attachments, allowed mentions, the poll or the components is invalid
+    pub fn try_build(mut self) -> Result<Webhook<'a>, ValidationError> {
+        if let Some(policy) = &self.truncation {
+            self.inner.truncate(policy);
+        }
+
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
     /// Build the Webhook
     ///
     /// # Panics
-    /// - If more than one of `content`, `file`, or `embeds` has been set
-    /// - If there are more than 10 embeds
+    /// If the Webhook is invalid, see [Self::try_build]
     pub fn build(self) -> Webhook<'a> {
-        let content = self.inner.content.is_some();
-        let file = self.inner.file.is_some();
-        let embeds = self.inner.embeds.is_some();
-
-        if !(content ^ file ^ embeds) {
-            #[cfg(not(feature = "no-panic"))]
-            {
-                panic!("You can only supply one of 'file', 'content' or 'embeds', not more than one.");
+        unwrap_build(self.try_build())
+    }
+
+    /// Build the Webhook as the changes to a message, see [crate::WebhookRequest::edit_url].
+    /// An edit only contains the fields to change, so nothing has to be set, e.g. `set_embeds(Vec::new())` alone removes the embeds of the message
+    ///
+    /// # Errors
+    /// - If any of the fields which are set is invalid, see [Self::try_build]
+    pub fn try_build_edit(mut self) -> Result<Webhook<'a>, ValidationError> {
+        if let Some(policy) = &self.truncation {
+            self.inner.truncate(policy);
+        }
+
+        let mut violations = Vec::new();
+        self.inner.collect_field_violations("", &mut violations);
+
+        if violations.is_empty() {
+            Ok(self.inner)
+        } else {
+            Err(ValidationError { violations })
+        }
+    }
+
+    /// Build the Webhook as the changes to a message
+    ///
+    /// # Panics
+    /// If the Webhook is invalid, see [Self::try_build_edit]
+    pub fn build_edit(self) -> Webhook<'a> {
+        unwrap_build(self.try_build_edit())
+    }
+}
+
+impl<'a> Webhook<'a> {
+    /// Whether the webhook has anything to show in the message: content, embeds, attachments, a poll or components
+    fn has_body(&self) -> bool {
+        let content = self.content.as_ref().map(|content| !content.is_empty()).unwrap_or_default();
+        let embeds = self.embeds.as_ref().map(|embeds| !embeds.is_empty()).unwrap_or_default();
+        let attachments = self.attachments.as_ref().map(|attachments| !attachments.is_empty()).unwrap_or_default();
+
+        let components = self.components.as_ref().map(|components| !components.is_empty()).unwrap_or_default();
+
+        content || embeds || attachments || components || self.poll.is_some()
+    }
+
+    /// Whether the content or embeds exceed what can be send in a single message
+    pub(crate) fn exceeds_message_limits(&self) -> bool {
+        let content = self.content.as_ref().map(|content| content.chars().count()).unwrap_or_default();
+        let embeds = self.embeds.as_deref().unwrap_or_default();
+        let embeds_length = embeds.iter().map(Embed::character_count).sum::<usize>();
+
+        content > limits::CONTENT_LENGTH || embeds.len() > limits::EMBEDS || embeds_length > limits::EMBEDS_TOTAL_LENGTH
+    }
+
+    /// Shorten every text exceeding Discord's limits according to `policy`
+    pub(crate) fn truncate(&mut self, policy: &TruncationPolicy) {
+        if let Some(content) = &mut self.content {
+            policy.truncate_cow(content, limits::CONTENT_LENGTH);
+        }
+
+        for attachment in self.attachments.iter_mut().flatten() {
+            if let Some(description) = &mut attachment.description {
+                policy.truncate_string(description, limits::ATTACHMENT_DESCRIPTION_LENGTH);
             }
         }
 
-        if let Some(embeds) = &self.inner.embeds {
-            if embeds.len() > 10 {
-                #[cfg(not(feature = "no-panic"))]
-                {
-                    panic!("You may supply up to 10 embeds, not more");
+        if let Some(embeds) = &mut self.embeds {
+            for embed in embeds.iter_mut() {
+                embed.truncate_to_fit(policy);
+            }
+
+            // Embeds which are split over multiple messages do not share a budget
+            if self.split {
+                return;
+            }
+
+            let lengths = embeds.iter()
+                .flat_map(Embed::text_lengths)
+                .collect::<Vec<_>>();
+
+            if let Some(cap) = budget_cap(&lengths, limits::EMBEDS_TOTAL_LENGTH) {
+                for embed in embeds.iter_mut() {
+                    embed.truncate(policy, cap);
                 }
             }
         }
+    }
 
-        self.inner
+    /// Check whether the webhook can be send to Discord
+    ///
+    /// # Errors
+    /// - If none of `content`, `embeds`, `attachments`, `poll` or `components` has been set
+    /// - If there are more than 10 embeds, and splitting is not allowed
+    /// - If any text exceeds Discord's limits
+    /// - If the thread name is empty, or tags are applied without a thread name or exceed 5
+    /// - If a flag is set which webhooks may not set
+    /// - If any of the embeds, attachments, allowed mentions, the poll or the components is invalid
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.check()
+    }
+}
+
+impl<'a> Validate for Webhook<'a> {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.collect_body_violations(path, violations);
+        self.collect_field_violations(path, violations);
+    }
+}
+
+impl<'a> Webhook<'a> {
+    /// Check that the webhook has anything to show in the message
+    pub(crate) fn collect_body_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if !self.has_body() {
+            violations.push(violation(path.to_string(), "At least one of 'content', 'embeds', 'attachments', 'poll' or 'components' must be set"));
+        }
+    }
+
+    /// Check every field which is set, without requiring anything to be set, as an edit only contains the fields to change
+    pub(crate) fn collect_field_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(content) = &self.content {
+            if !self.split {
+                check_length(content, limits::CONTENT_LENGTH, field_path(path, "content"), violations);
+            }
+        }
+
+        if let Some(embeds) = &self.embeds {
+            let embeds_path = field_path(path, "embeds");
+            if !self.split && embeds.len() > limits::EMBEDS {
+                violations.push(violation(embeds_path.clone(), format!("There may be up to {} embeds", limits::EMBEDS)));
+            }
+
+            for (idx, embed) in embeds.iter().enumerate() {
+                embed.collect_violations(&index_path(&embeds_path, idx), violations);
+            }
+
+            let character_count = embeds.iter().map(Embed::character_count).sum::<usize>();
+            if !self.split && embeds.len() > 1 && character_count > limits::EMBEDS_TOTAL_LENGTH {
+                violations.push(violation(embeds_path, format!("The embeds may contain up to {} characters combined, but contain {}", limits::EMBEDS_TOTAL_LENGTH, character_count)));
+            }
+        }
+
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            allowed_mentions.collect_violations(&field_path(path, "allowed_mentions"), violations);
+        }
+
+        for (idx, attachment) in self.attachments.iter().flatten().enumerate() {
+            attachment.collect_violations(&index_path(&field_path(path, "attachments"), idx), violations);
+        }
+
+        if let Some(poll) = &self.poll {
+            poll.collect_violations(&field_path(path, "poll"), violations);
+        }
+
+        if let Some(components) = &self.components {
+            collect_component_violations(components, &field_path(path, "components"), violations);
+        }
+
+        if let Some(flags) = self.flags {
+            let disallowed = flags.disallowed_for_webhooks();
+            if !disallowed.is_empty() {
+                violations.push(violation(field_path(path, "flags"), format!("Webhooks may only set {}, not {}", MessageFlags::WEBHOOK_ALLOWED, disallowed)));
+            }
+        }
+
+        if let Some(thread_name) = &self.thread_name {
+            let thread_name_path = field_path(path, "thread_name");
+            if thread_name.is_empty() {
+                violations.push(violation(thread_name_path.clone(), "May not be empty"));
+            }
+
+            check_length(thread_name, limits::THREAD_NAME_LENGTH, thread_name_path, violations);
+        }
+
+        if let Some(applied_tags) = &self.applied_tags {
+            let applied_tags_path = field_path(path, "applied_tags");
+            if self.thread_name.is_none() {
+                violations.push(violation(applied_tags_path.clone(), "Tags can only be applied when creating a forum post with 'thread_name'"));
+            }
+
+            if applied_tags.len() > limits::APPLIED_TAGS {
+                violations.push(violation(applied_tags_path, format!("There may be up to {} tags", limits::APPLIED_TAGS)));
+            }
+        }
     }
 }
 
 /// The allowed mention object allows for more granular control over mentions without various hacks to the message content
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
 pub struct AllowedMention<'a> {
     /// An array of allowed mention types to parse from the content.
     pub parse:              Vec<AllowedMentionType>,
     /// Array of role_ids to mention (Max size of 100)
-    pub roles:              Vec<&'a str>,
+    pub roles:              Vec<Cow<'a, str>>,
     /// Array of user_ids to mention (Max size of 100)
-    pub users:              Vec<&'a str>,
+    pub users:              Vec<Cow<'a, str>>,
     /// For replies, whether to mention the author of the message being replied to (default false)
     pub replied_user:       bool
 }
@@ -141,27 +398,13 @@ impl<'a> AllowedMentionBuilder<'a> {
 
     /// Array of role_ids to mention (Max size of 100)
     pub fn set_roles(mut self, roles: Vec<&'a str>) -> Self {
-        if roles.len() > 100 {
-            #[cfg(not(feature = "no-panic"))]
-            {
-                panic!("The max size for the roles vec is 100");
-            }
-        }
-
-        self.inner.roles = roles;
+        self.inner.roles = roles.into_iter().map(Cow::Borrowed).collect();
         self
     }
 
     /// Array of user_ids to mention (Max size of 100)
     pub fn set_users(mut self, users: Vec<&'a str>) -> Self {
-        if users.len() > 100 {
-            #[cfg(not(feature = "no-panic"))]
-            {
-                panic!("The max size for the users vec is 100");
-            }
-        }
-
-        self.inner.users = users;
+        self.inner.users = users.into_iter().map(Cow::Borrowed).collect();
         self
     }
 
@@ -171,22 +414,269 @@ impl<'a> AllowedMentionBuilder<'a> {
         self
     }
 
-    /// Build the Builder
+    /// Build the AllowedMention
+    ///
+    /// # Errors
+    /// - If there are more than 100 roles or users
+    /// - If roles or users are listed, while also being parsed from the content
+    pub fn try_build(self) -> Result<AllowedMention<'a>, ValidationError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+
+    /// Build the AllowedMention
+    ///
+    /// # Panics
+    /// If the AllowedMention is invalid, see [Self::try_build]
     pub fn build(self) -> AllowedMention<'a> {
-        self.inner
+        unwrap_build(self.try_build())
+    }
+}
+
+impl<'a> Validate for AllowedMention<'a> {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let parse_roles = self.parse.iter().any(|parse| matches!(parse, AllowedMentionType::RoleMention));
+        let parse_users = self.parse.iter().any(|parse| matches!(parse, AllowedMentionType::UserMention));
+
+        if self.roles.len() > limits::ALLOWED_MENTION_IDS {
+            violations.push(violation(field_path(path, "roles"), format!("There may be up to {} roles", limits::ALLOWED_MENTION_IDS)));
+        }
+
+        if parse_roles && !self.roles.is_empty() {
+            violations.push(violation(field_path(path, "roles"), "Roles may not be listed when role mentions are parsed"));
+        }
+
+        if self.users.len() > limits::ALLOWED_MENTION_IDS {
+            violations.push(violation(field_path(path, "users"), format!("There may be up to {} users", limits::ALLOWED_MENTION_IDS)));
+        }
+
+        if parse_users && !self.users.is_empty() {
+            violations.push(violation(field_path(path, "users"), "Users may not be listed when user mentions are parsed"));
+        }
     }
 }
 
 /// The type of Allowed Mentions
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum AllowedMentionType {
     /// Controls role mentions
-    #[serde(rename(serialize = "roles"))]
+    #[serde(rename = "roles")]
     RoleMention,
     /// Controls user mentions
-    #[serde(rename(serialize = "users"))]
+    #[serde(rename = "users")]
     UserMention,
     /// Controls @everyone and @here mentions
-    #[serde(rename(serialize = "everyone"))]
+    #[serde(rename = "everyone")]
     EveryoneMention
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn violations_have_paths() {
+        let error = WebhookBuilder::new()
+            .set_embeds(vec![
+                EmbedBuilder::new()
+                    .set_title("Valid")
+                    .build(),
+                Embed {
+                    fields: Some(vec![
+                        EmbedField {
+                            name:   "Name".to_string(),
+                            value:  String::new(),
+                            inline: None
+                        }
+                    ]),
+                    ..Default::default()
+                }
+            ])
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations, vec![Violation {
+            path:       "embeds[1].fields[0].value".to_string(),
+            message:    "A field must have a value".to_string()
+        }]);
+    }
+
+    #[test]
+    fn allowed_mention_limits() {
+        let users = vec!["1"; 101];
+        let error = AllowedMentionBuilder::new()
+            .set_parse(vec![AllowedMentionType::UserMention])
+            .set_users(users)
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations.len(), 2);
+        assert!(error.violations.iter().all(|violation| violation.path == "users"));
+    }
+
+    #[test]
+    fn embeds_total_length_limit() {
+        let embed = EmbedBuilder::new()
+            .set_description("a".repeat(4000))
+            .build();
+
+        let error = WebhookBuilder::new()
+            .set_embeds(vec![embed.clone(), embed])
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations.len(), 1);
+        assert_eq!(error.violations[0].path, "embeds");
+    }
+
+    #[test]
+    fn content_embeds_and_attachments_combined() {
+        let webhook = WebhookBuilder::new()
+            .set_content("<@&123> Deploy failed")
+            .set_embeds(vec![
+                EmbedBuilder::new()
+                    .set_title("Deploy")
+                    .build()
+            ])
+            .set_attachments(vec![
+                AttachmentBuilder::new()
+                    .set_file_name("deploy.log")
+                    .set_data("Hello world!")
+                    .build()
+            ])
+            .try_build();
+
+        assert!(webhook.is_ok());
+    }
+
+    #[test]
+    fn empty_webhook() {
+        let error = WebhookBuilder::new()
+            .set_content("")
+            .set_username("CI")
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(error.violations.len(), 1);
+        assert_eq!(error.violations[0].path, "");
+    }
+
+    #[test]
+    fn forum_post_payload() {
+        let webhook = WebhookBuilder::new()
+            .set_content("Database unreachable")
+            .set_thread_name("Incident 42")
+            .set_applied_tags(vec!["1", "2"])
+            .build();
+
+        let payload = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(payload["thread_name"], "Incident 42");
+        assert_eq!(payload["applied_tags"], serde_json::json!(["1", "2"]));
+
+        let payload = serde_json::to_value(WebhookBuilder::new().set_content("Hello").build()).unwrap();
+        assert!(payload.get("thread_name").is_none());
+        assert!(payload.get("applied_tags").is_none());
+    }
+
+    #[test]
+    fn invalid_forum_post() {
+        let err = WebhookBuilder::new()
+            .set_content("Hello")
+            .set_applied_tags(vec!["1", "2", "3", "4", "5", "6"])
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(err.violations.len(), 2);
+        assert!(err.violations.iter().all(|v| v.path == "applied_tags"));
+
+        let name = "a".repeat(101);
+        let err = WebhookBuilder::new().set_content("Hello").set_thread_name(&name).try_build().unwrap_err();
+        assert_eq!(err.violations[0].path, "thread_name");
+        assert!(WebhookBuilder::new().set_content("Hello").set_thread_name("").try_build().is_err());
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Hello").set_thread_name("Incident").build())
+            .build();
+
+        let in_thread = WebhookUrl::new("1", "token").unwrap().with_thread_id("2").unwrap();
+        assert_eq!(req.validate_thread(&in_thread).unwrap_err().violations[0].path, "thread_name");
+        assert!(req.validate_thread(&WebhookUrl::new("1", "token").unwrap()).is_ok());
+        assert_eq!(req.validate_edit().unwrap_err().violations[0].path, "thread_name");
+    }
+
+    #[test]
+    fn webhook_round_trip() {
+        let footer = EmbedFooterBuilder::new().set_text("Footer").set_icon_url("https://example.com/icon.png").build();
+        let author = EmbedAuthorBuilder::new().set_name("Author").set_url("https://example.com").build();
+        let image = EmbedImageBuilder::new().set_url("https://example.com/image.png").set_width(64).set_height(64).build();
+        let allowed_mentions = AllowedMentionBuilder::new().set_parse(vec![AllowedMentionType::UserMention]).set_roles(vec!["1"]).build();
+        let button = ButtonBuilder::new().set_label("View logs").set_url("https://ci.example.com/logs/42").build();
+        let select = SelectMenuBuilder::new()
+            .set_custom_id("environment")
+            .add_option(SelectOptionBuilder::new().set_label("Staging").set_value("staging").build())
+            .build();
+
+        let webhook = WebhookBuilder::new()
+            .set_content("Deployed")
+            .set_username("CI")
+            .set_avatar_url("https://example.com/avatar.png")
+            .set_embeds(vec![EmbedBuilder::new()
+                .set_title("Title")
+                .set_color_hex("#ff0000")
+                .set_footer(&footer)
+                .set_author(&author)
+                .set_image(&image)
+                .set_fields(vec![EmbedFieldBuilder::new().set_name("Name").set_value("Value").set_inline(true).build()])
+                .build()])
+            .set_allowed_mentions(&allowed_mentions)
+            .set_poll(PollBuilder::new().set_question("Ship it?").add_answer(PollAnswerBuilder::new().set_text("Yes").build()).build())
+            .set_components(vec![
+                ActionRowBuilder::new().add_button(button).build(),
+                ActionRowBuilder::new().add_select_menu(select).build()
+            ])
+            .set_flags(MessageFlags::SUPPRESS_NOTIFICATIONS)
+            .set_thread_name("Deploy 42")
+            .set_applied_tags(vec!["7"])
+            .build();
+
+        let json = serde_json::to_string(&webhook).unwrap();
+        let parsed: Webhook<'static> = serde_json::from_str(&json).unwrap();
+        assert!(parsed.validate().is_ok());
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+        let embed = &parsed.embeds.as_ref().unwrap()[0];
+        assert_eq!(embed.footer.as_ref().unwrap().text, "Footer");
+        assert_eq!(parsed.allowed_mentions.as_ref().unwrap().roles, ["1"]);
+        assert!(matches!(parsed.components.as_ref().unwrap()[1].components[0], ActionRowComponent::SelectMenu(_)));
+    }
+
+    #[test]
+    fn webhook_from_template() {
+        let template = r#"{
+            "content": "Deploy finished",
+            "embeds": [{ "title": "Release", "fields": [{ "name": "Version", "value": "1.2.3" }] }],
+            "allowed_mentions": { "parse": ["users", "everyone"], "roles": [], "users": [], "replied_user": false }
+        }"#;
+
+        let webhook: Webhook<'static> = serde_json::from_str(template).unwrap();
+        assert!(webhook.validate().is_ok());
+        assert_eq!(webhook.content.as_deref(), Some("Deploy finished"));
+
+        let embed = &webhook.embeds.as_ref().unwrap()[0];
+        assert_eq!(embed.r#type, "rich");
+        assert_eq!(embed.fields.as_ref().unwrap()[0].value, "1.2.3");
+
+        let parse = &webhook.allowed_mentions.as_ref().unwrap().parse;
+        assert!(matches!(parse[..], [AllowedMentionType::UserMention, AllowedMentionType::EveryoneMention]));
+
+        let req = WebhookRequestBuilder::new().set_data(webhook).build();
+        assert!(req.validate().is_ok());
+
+        // Fields Discord does not require may be left out
+        let webhook: Webhook<'static> = serde_json::from_str(r#"{"content": "x", "allowed_mentions": {"parse": []}}"#).unwrap();
+        assert!(webhook.validate().is_ok());
+        let allowed_mentions = webhook.allowed_mentions.as_ref().unwrap();
+        assert!(allowed_mentions.roles.is_empty() && allowed_mentions.users.is_empty() && !allowed_mentions.replied_user);
+    }
+}
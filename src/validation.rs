@@ -0,0 +1,72 @@
+//! Helpers for validating payloads against Discord's requirements
+
+use crate::{ValidationError, Violation};
+
+/// A value which can be checked against Discord's requirements
+pub(crate) trait Validate {
+    /// Add every requirement violated by this value to `violations`.
+    /// `path` is the path of this value within the payload, empty if it is the root
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>);
+
+    /// Check every requirement of this value
+    ///
+    /// # Errors
+    /// - When one or more requirements are violated
+    fn check(&self) -> Result<(), ValidationError> {
+        let mut violations = Vec::new();
+        self.collect_violations("", &mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { violations })
+        }
+    }
+}
+
+/// Path of the field `field` of the value at `path`, e.g. `embeds[2].title`
+pub(crate) fn field_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Path of the element at `idx` of the list at `path`, e.g. `embeds[2]`
+pub(crate) fn index_path(path: &str, idx: usize) -> String {
+    format!("{}[{}]", path, idx)
+}
+
+/// Create a violation of the value at `path`
+pub(crate) fn violation<S: AsRef<str>>(path: String, message: S) -> Violation {
+    Violation {
+        path,
+        message: message.as_ref().to_string()
+    }
+}
+
+/// Check that `url`, if set, uses one of the `schemes`, e.g. `https://`
+pub(crate) fn check_url_scheme(url: Option<&str>, schemes: &[&str], path: String, violations: &mut Vec<Violation>) {
+    if let Some(url) = url {
+        if !schemes.iter().any(|scheme| url.starts_with(scheme)) {
+            violations.push(violation(path, format!("'{}' must start with one of {}", url, schemes.join(", "))));
+        }
+    }
+}
+
+/// Check that `value` is at most `limit` characters long
+pub(crate) fn check_length(value: &str, limit: usize, path: String, violations: &mut Vec<Violation>) {
+    let length = value.chars().count();
+    if length > limit {
+        violations.push(violation(path, format!("Must be {} or fewer characters long, but is {}", limit, length)));
+    }
+}
+
+/// Unwrap the result of a builder's `try_build`, panicking with the validation error if it failed
+pub(crate) fn unwrap_build<T>(result: Result<T, ValidationError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{}", e)
+    }
+}
@@ -1,21 +1,43 @@
 //! Module containing a webhook's execution logic
 
-use crate::Webhook;
-use reqwest::{Response, Error};
-use lazy_static::lazy_static;
-
-lazy_static! {
-    /// HTTP client to be reused for every webhook call
-    static ref HTTP_CLIENT: reqwest::Client = reqwest::Client::new();
-}
+use std::borrow::Cow;
+use crate::{Webhook, WebhookUrl, IntoWebhookUrl, Embed, AllowedMention, Attachment, Message, Error, ValidationError, Violation, split_content, batch_embeds};
+use crate::{ActionRow, EditAttachment, edit_attachments};
+use crate::validation::{Validate, field_path, index_path, violation, unwrap_build};
+use crate::limits;
+use serde::Serialize;
+use crate::WebhookClient;
+use reqwest::{Response, RequestBuilder};
+use reqwest::multipart::{Form, Part};
 
 /// A webhook request
 #[derive(Default, Clone, Debug)]
 pub struct WebhookRequest<'a> {
     /// The Webhook's payload
     pub data:       Webhook<'a>,
-    /// The name of the File to be send, if there is any
-    pub file_name:  Option<String>
+    /// ids of the attachments to keep when editing a message.
+    /// If set, or if files are uploaded with the edit, the attachments of the message which are not listed are removed
+    pub keep_attachments: Option<Vec<String>>,
+}
+
+/// The payload of a message edit, only containing the fields to be changed
+#[derive(Serialize)]
+pub(crate) struct EditPayload<'r, 'a> {
+    /// the message contents (up to 2000 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content:            Option<&'r str>,
+    /// embedded rich content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds:             Option<&'r [Embed<'a>]>,
+    /// allowed mentions for the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions:   Option<&'r AllowedMention<'a>>,
+    /// rows of buttons and select menus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components:         Option<&'r [ActionRow]>,
+    /// attachments to keep, and the files being sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments:        Option<Vec<EditAttachment<'r>>>
 }
 
 /// Builder for WebhookRequest
@@ -37,100 +59,1143 @@ impl<'a> WebhookRequestBuilder<'a> {
         self
     }
 
-    /// Set the filename to be used for an uploaded file
-    pub fn set_file_name<S: 'a + AsRef<str>>(mut self, file_name: S) -> Self {
-        self.inner.file_name = Some(file_name.as_ref().to_string());
+    /// Add a file to be uploaded with the message.
+    /// This appends to the attachments of the webhook data, so it should be called after [Self::set_data]
+    pub fn add_attachment(mut self, attachment: Attachment) -> Self {
+        self.inner.data.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    /// Keep the attachment with the given id when editing a message.
+    /// Attachments of the message which are not kept are removed, if any attachment is kept or uploaded
+    pub fn keep_attachment<S: AsRef<str>>(mut self, id: S) -> Self {
+        self.inner.keep_attachments.get_or_insert_with(Vec::new).push(id.as_ref().to_string());
+        self
+    }
+
+    /// Keep none of the attachments of the message when editing it, removing all of them
+    pub fn remove_attachments(mut self) -> Self {
+        self.inner.keep_attachments = Some(Vec::new());
         self
     }
 
+    /// Build the WebhookRequest.
+    /// The request may lack content, embeds and the like, to only change other fields when editing a message,
+    /// but executing such a request fails
+    ///
+    /// # Errors
+    /// - If any of the fields of the webhook data is invalid, see [crate::WebhookBuilder::try_build]
+    /// - If an embed refers to an attachment which is not part of the request
+    /// - If two attachments have the same file name, see [Attachment::upload_file_name]
+    pub fn try_build(self) -> Result<WebhookRequest<'a>, ValidationError> {
+        let mut violations = Vec::new();
+        self.inner.collect_field_violations("", &mut violations);
+
+        if violations.is_empty() {
+            Ok(self.inner)
+        } else {
+            Err(ValidationError { violations })
+        }
+    }
+
     /// Build the WebhookRequest
     ///
     /// # Panics
-    /// Panics if a file name has not been set and a file is to be uploaded
+    /// If the WebhookRequest is invalid, see [Self::try_build]
     pub fn build(self) -> WebhookRequest<'a> {
-        if self.inner.data.file.is_some() && self.inner.file_name.is_none() {
-            #[cfg(not(feature = "no-panic"))]
-            {
-                panic!("You must set a file name if you are uploading a file");
-            }
+        unwrap_build(self.try_build())
+    }
+}
+
+impl<'a> Validate for WebhookRequest<'a> {
+    fn collect_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.data.collect_body_violations(path, violations);
+        self.collect_field_violations(path, violations);
+    }
+}
+
+impl<'a> WebhookRequest<'a> {
+    /// Check every field which is set, without requiring the message to have a body, see [Webhook::collect_field_violations]
+    fn collect_field_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.data.collect_field_violations(path, violations);
+        self.collect_file_name_violations(path, violations);
+
+        if !self.needs_split() {
+            self.collect_attachment_violations(path, violations);
+            return;
         }
 
-        self.inner
+        // Embeds can only refer to attachments send in the same message
+        for (idx, request) in self.split().iter().enumerate() {
+            request.collect_attachment_violations(&index_path(&field_path(path, "messages"), idx), violations);
+        }
     }
 }
 
 impl<'a> WebhookRequest<'a> {
-    /// Execute the current webhook request to the target URL
+    /// The payload when editing a message with this request
+    pub(crate) fn edit_payload(&self) -> EditPayload<'_, 'a> {
+        let uploads = self.data.attachments.as_deref().unwrap_or_default();
+        let attachments = match &self.keep_attachments {
+            Some(keep) => Some(edit_attachments(keep, uploads)),
+            None if !uploads.is_empty() => Some(edit_attachments(&[], uploads)),
+            None => None
+        };
+
+        EditPayload {
+            content:            self.data.content.as_deref(),
+            embeds:             self.data.embeds.as_deref(),
+            allowed_mentions:   self.data.allowed_mentions.as_deref(),
+            components:         self.data.components.as_deref(),
+            attachments
+        }
+    }
+
+    /// Check whether the request can be send to Discord
     ///
     /// # Errors
-    /// - When the request fails
-    pub async fn execute_url<S: AsRef<str>>(&self, url: S) -> Result<Response, Error> {
-        let mut req_builder = HTTP_CLIENT.post(url.as_ref())
-            .json(&self.data);
+    /// - If none of `content`, `embeds`, `attachments`, `poll` or `components` has been set
+    /// - If the request is invalid, see [WebhookRequestBuilder::try_build]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.check()
+    }
+
+    /// Check that every `attachment://` URL used in an embed refers to one of the attachments to be uploaded
+    fn collect_attachment_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let attachment_urls = self.data.attachments.iter()
+            .flatten()
+            .map(Attachment::attachment_url)
+            .collect::<Vec<_>>();
+
+        for (idx, embed) in self.data.embeds.iter().flatten().enumerate() {
+            for reference in embed.attachment_references() {
+                if !attachment_urls.iter().any(|url| url == reference) {
+                    let embed_path = index_path(&field_path(path, "embeds"), idx);
+                    violations.push(violation(embed_path, format!("'{}' does not refer to an attachment of this message", reference)));
+                }
+            }
+        }
+    }
+
+    /// Check that no two attachments are uploaded with the same file name, as `attachment://` URLs could not tell them apart
+    fn collect_file_name_violations(&self, path: &str, violations: &mut Vec<Violation>) {
+        let attachments = self.data.attachments.as_deref().unwrap_or_default();
+        for (idx, attachment) in attachments.iter().enumerate() {
+            let file_name = attachment.upload_file_name();
+            if attachments[..idx].iter().any(|other| other.upload_file_name() == file_name) {
+                let attachment_path = index_path(&field_path(path, "attachments"), idx);
+                violations.push(violation(field_path(&attachment_path, "file_name"), format!("Another attachment is already named '{}'", file_name)));
+            }
+        }
+    }
+
+    /// Whether the content or embeds exceed the limits, and are to be split into multiple messages
+    fn needs_split(&self) -> bool {
+        self.data.split && self.data.exceeds_message_limits()
+    }
+
+    /// Check whether the request can be send to Discord as a single message
+    ///
+    /// # Errors
+    /// - If the request is invalid, see [WebhookRequestBuilder::try_build]
+    /// - If the content has to be split into multiple messages
+    pub(crate) fn validate_single(&self) -> Result<(), ValidationError> {
+        self.validate()?;
+
+        if self.needs_split() {
+            return Err(ValidationError {
+                violations: vec![violation(
+                    String::new(),
+                    "The content or embeds exceed the limits of a single message, use execute_url_split instead"
+                )]
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the request can be used to edit a message
+    ///
+    /// # Errors
+    /// - If the request is invalid, see [WebhookRequestBuilder::try_build]
+    /// - If the content has to be split into multiple messages
+    /// - If the username, avatar, TTS, flags, poll, thread name or tags are set, which cannot be changed when editing
+    pub(crate) fn validate_edit(&self) -> Result<(), ValidationError> {
+        let mut violations = Vec::new();
+        self.collect_field_violations("", &mut violations);
+
+        if self.needs_split() {
+            violations.push(violation(String::new(), "The content or embeds exceed the limits of a single message, which cannot be split when editing"));
+        }
+
+        let fixed = [
+            ("username", self.data.username.is_some()),
+            ("avatar_url", self.data.avatar_url.is_some()),
+            ("tts", self.data.tts.is_some()),
+            ("flags", self.data.flags.is_some()),
+            ("poll", self.data.poll.is_some()),
+            ("thread_name", self.data.thread_name.is_some()),
+            ("applied_tags", self.data.applied_tags.is_some())
+        ];
+
+        for (field, is_set) in fixed.iter() {
+            if *is_set {
+                violations.push(violation(field.to_string(), "Cannot be changed when editing a message"));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { violations })
+        }
+    }
+
+    /// Check that the request does not create a thread, while the webhook URL targets one
+    ///
+    /// # Errors
+    /// - If the thread name is set and the URL has a thread id
+    pub(crate) fn validate_thread(&self, url: &WebhookUrl) -> Result<(), ValidationError> {
+        if url.thread_id().is_some() && self.data.thread_name.is_some() {
+            return Err(ValidationError {
+                violations: vec![violation("thread_name".to_string(), "Cannot create a thread when the webhook URL targets a thread")]
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The requests for every message this request is to be send as.
+    /// If the webhook allows splitting and its content or embeds exceed the limits, the content is split with [split_content]
+    /// and the embeds are packed into as few messages as possible with [batch_embeds]. The messages with content come first,
+    /// the last of them carrying the first embeds. The attachments, poll and components are send with the last message.
+    /// If a forum post is created, only the first message creates it
+    pub fn split(&self) -> Vec<WebhookRequest<'a>> {
+        if !self.needs_split() {
+            return vec![self.clone()];
+        }
+
+        let template = Webhook {
+            username:           self.data.username.clone(),
+            avatar_url:         self.data.avatar_url.clone(),
+            tts:                self.data.tts,
+            allowed_mentions:   self.data.allowed_mentions.clone(),
+            flags:              self.data.flags,
+            ..Default::default()
+        };
+
+        let mut messages = match &self.data.content {
+            Some(content) => split_content(content, limits::CONTENT_LENGTH).into_iter()
+                .map(|chunk| Webhook { content: Some(Cow::Owned(chunk)), ..template.clone() })
+                .collect(),
+            None => Vec::new()
+        };
+
+        let batches = batch_embeds(self.data.embeds.clone().unwrap_or_default());
+        for (idx, batch) in batches.into_iter().enumerate() {
+            match messages.last_mut() {
+                Some(message) if idx == 0 => message.embeds = Some(batch),
+                _ => messages.push(Webhook { embeds: Some(batch), ..template.clone() })
+            }
+        }
+
+        match messages.last_mut() {
+            Some(message) => {
+                message.attachments = self.data.attachments.clone();
+                message.poll = self.data.poll.clone();
+                message.components = self.data.components.clone();
+            },
+            None => messages.push(Webhook {
+                attachments:    self.data.attachments.clone(),
+                poll:           self.data.poll.clone(),
+                components:     self.data.components.clone(),
+                ..template
+            })
+        }
+
+        if let Some(message) = messages.first_mut() {
+            message.thread_name = self.data.thread_name.clone();
+            message.applied_tags = self.data.applied_tags.clone();
+        }
+
+        messages.into_iter()
+            .map(|data| WebhookRequest { data, ..Default::default() })
+            .collect()
+    }
+
+    /// The files to be uploaded, if there are any
+    fn files(&self) -> Option<&[Attachment]> {
+        self.data.attachments.as_deref().filter(|attachments| !attachments.is_empty())
+    }
+
+    /// Attach `payload` to the request. If files are to be uploaded, the request is send as
+    /// `multipart/form-data`, with the JSON payload in the `payload_json` part and the n-th file in the `files[n]` part.
+    /// Otherwise the payload is send as JSON
+    ///
+    /// # Errors
+    /// - When an attachment's content type is not a valid media type
+    pub(crate) fn attach_payload<T: Serialize>(&self, req_builder: RequestBuilder, payload: &T) -> Result<RequestBuilder, Error> {
+        let files = match self.files() {
+            Some(files) => files,
+            None => return Ok(req_builder.json(payload))
+        };
+
+        let mut form = Form::new()
+            .text("payload_json", payload_json(payload));
+
+        for (idx, attachment) in files.iter().enumerate() {
+            let part = Part::bytes(attachment.data.clone())
+                .file_name(attachment.upload_file_name())
+                .mime_str(&attachment.mime_type())?;
 
-        if let Some(f) = &self.file_name {
-            req_builder = req_builder.header("Content-Disposition", f);
+            form = form.part(format!("files[{}]", idx), part);
         }
 
-        req_builder.send().await
+        Ok(req_builder.multipart(form))
+    }
+
+    /// Execute the current webhook request to the target URL
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    pub async fn execute_url<U: IntoWebhookUrl>(&self, url: U) -> Result<Response, Error> {
+        WebhookClient::shared().execute(self, url).await
     }
 
     /// Execute the current webhook request to a target URL build from the given `id` and `token`
     ///
     /// # Errors
+    /// - When the request is invalid
     /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
     pub async fn execute<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Response, Error> {
-        let mut req_builder = HTTP_CLIENT.post(format!("https://discord.com/api/webhooks/{}/{}", id.as_ref(), token.as_ref()))
-            .json(&self.data);
+        self.execute_url(WebhookUrl::new(id, token)?).await
+    }
+
+    /// Execute the current webhook request to the target URL, waiting for Discord to confirm the message has been created
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn execute_url_wait<U: IntoWebhookUrl>(&self, url: U) -> Result<Message, Error> {
+        WebhookClient::shared().execute_wait(self, url).await
+    }
+
+    /// Execute the current webhook request to a target URL build from the given `id` and `token`,
+    /// waiting for Discord to confirm the message has been created
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn execute_wait<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Message, Error> {
+        self.execute_url_wait(WebhookUrl::new(id, token)?).await
+    }
+
+    /// Execute the current webhook request to the target URL, splitting content and embeds exceeding the limits into multiple messages,
+    /// see [WebhookClient::execute_split]
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request for any of the messages fails, in which case the remaining messages are not send
+    /// - When Discord responds with an unsuccessful status code
+    /// - When a returned message could not be deserialized
+    pub async fn execute_url_split<U: IntoWebhookUrl>(&self, url: U) -> Result<Vec<Message>, Error> {
+        WebhookClient::shared().execute_split(self, url).await
+    }
+
+    /// Execute the current webhook request to a target URL build from the given `id` and `token`,
+    /// splitting content and embeds exceeding the limits into multiple messages, see [Self::execute_url_split]
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request for any of the messages fails, in which case the remaining messages are not send
+    /// - When Discord responds with an unsuccessful status code
+    /// - When a returned message could not be deserialized
+    pub async fn execute_split<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Vec<Message>, Error> {
+        self.execute_url_split(WebhookUrl::new(id, token)?).await
+    }
+
+    /// Edit the message with the given id, previously send by the webhook at the target URL, see [WebhookClient::edit]
+    ///
+    /// # Errors
+    /// - When the request is invalid, or sets a field which cannot be edited
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn edit_url<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<Message, Error> {
+        WebhookClient::shared().edit(self, url, message_id).await
+    }
+
+    /// Edit the message with the given id, previously send by the webhook with the given `id` and `token`, see [Self::edit_url]
+    ///
+    /// # Errors
+    /// - When the request is invalid, or sets a field which cannot be edited
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn edit<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(&self, id: A, token: B, message_id: M) -> Result<Message, Error> {
+        self.edit_url(WebhookUrl::new(id, token)?, message_id).await
+    }
+
+    /// Get the message with the given id, previously send by the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    /// - When the returned message could not be deserialized
+    pub async fn get_message_url<U: IntoWebhookUrl, M: AsRef<str>>(url: U, message_id: M) -> Result<Message, Error> {
+        WebhookClient::shared().get_message(url, message_id).await
+    }
+
+    /// Get the message with the given id, previously send by the webhook with the given `id` and `token`
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    /// - When the returned message could not be deserialized
+    pub async fn get_message<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(id: A, token: B, message_id: M) -> Result<Message, Error> {
+        Self::get_message_url(WebhookUrl::new(id, token)?, message_id).await
+    }
+
+    /// Delete the message with the given id, previously send by the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    pub async fn delete_message_url<U: IntoWebhookUrl, M: AsRef<str>>(url: U, message_id: M) -> Result<(), Error> {
+        WebhookClient::shared().delete_message(url, message_id).await
+    }
+
+    /// Delete the message with the given id, previously send by the webhook with the given `id` and `token`
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    pub async fn delete_message<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(id: A, token: B, message_id: M) -> Result<(), Error> {
+        Self::delete_message_url(WebhookUrl::new(id, token)?, message_id).await
+    }
+}
+
+impl WebhookClient {
+    /// Execute the webhook request to the target URL.
+    /// Failures are retried according to the client's [crate::RetryPolicy], except for a response with status 500 or a timeout:
+    /// the message may have been posted regardless, so by default it is not sent again and may be lost
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    pub async fn execute<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Response, Error> {
+        request.validate_single()?;
+
+        let url = url.into_webhook_url()?;
+        request.validate_thread(&url)?;
+        self.send(request, &url, false).await
+    }
+
+    /// Execute the webhook request to the target URL, waiting for Discord to confirm the message has been created.
+    /// Failures are retried like with [Self::execute]
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn execute_wait<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Message, Error> {
+        request.validate_single()?;
 
-        if let Some(f) = &self.file_name {
-            req_builder = req_builder.header("Content-Disposition", f);
+        let url = url.into_webhook_url()?;
+        request.validate_thread(&url)?;
+        let response = self.send(request, &url, true).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Execute the webhook request to the target URL, splitting content and embeds exceeding the limits into multiple messages,
+    /// see [WebhookRequest::split]. The messages are send in order, waiting for each to be created before sending the next.
+    /// If the request creates a forum post, the following messages are send to the created post.
+    /// Failures are retried like with [Self::execute]
+    ///
+    /// # Errors
+    /// - When the request is invalid
+    /// - When the request for any of the messages fails, in which case the remaining messages are not send
+    /// - When Discord responds with an unsuccessful status code
+    /// - When a returned message could not be deserialized
+    pub async fn execute_split<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Vec<Message>, Error> {
+        request.validate()?;
+
+        let mut url = url.into_webhook_url()?;
+        request.validate_thread(&url)?;
+
+        let mut messages = Vec::new();
+        for request in request.split() {
+            let response = self.send(&request, &url, true).await?;
+            let message: Message = response.json().await?;
+
+            // The id of a forum post is the id of its channel
+            if request.data.thread_name.is_some() {
+                url = url.with_thread_id(&message.channel_id)?;
+            }
+
+            messages.push(message);
         }
 
-        req_builder.send().await
+        Ok(messages)
+    }
+
+    /// Send the request to the target URL, without validating it first.
+    /// If `wait` is set, Discord returns the created message
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    async fn send(&self, request: &WebhookRequest<'_>, url: &WebhookUrl, wait: bool) -> Result<Response, Error> {
+        let request_url = url.request_url(&self.api_url());
+        self.send_request(url, false, |http| request.attach_payload(http.post(&request_url).query(&message_query(request, wait)), &request.data)).await
+    }
+
+    /// Edit the message with the given id, previously send by the webhook at the target URL.
+    /// Only the content, embeds, allowed mentions, components and attachments which are set in the request are changed
+    ///
+    /// # Errors
+    /// - When the request is invalid, or sets a field which cannot be edited
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code
+    /// - When the returned message could not be deserialized
+    pub async fn edit<U: IntoWebhookUrl, M: AsRef<str>>(&self, request: &WebhookRequest<'_>, url: U, message_id: M) -> Result<Message, Error> {
+        request.validate_edit()?;
+
+        let url = url.into_webhook_url()?;
+        let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+        let response = self.send_request(&url, true, |http| request.attach_payload(http.patch(&message_url).query(&message_query(request, false)), &request.edit_payload())).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Get the message with the given id, previously send by the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    /// - When the returned message could not be deserialized
+    pub async fn get_message<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<Message, Error> {
+        let url = url.into_webhook_url()?;
+        let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+        let response = self.send_request(&url, true, |http| Ok(http.get(&message_url))).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Delete the message with the given id, previously send by the webhook at the target URL
+    ///
+    /// # Errors
+    /// - When the request fails
+    /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+    pub async fn delete_message<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<(), Error> {
+        let url = url.into_webhook_url()?;
+        let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+        self.send_request(&url, true, |http| Ok(http.delete(&message_url))).await?;
+        Ok(())
     }
 }
 
-#[cfg(feature = "blocking")]
-pub mod blocking {
-    use crate::WebhookRequest;
-    use reqwest::blocking::Response;
-    use reqwest::Error;
-    use lazy_static::lazy_static;
+/// The JSON payload, serialized for use as the `payload_json` part of a multipart body
+fn payload_json<T: Serialize>(payload: &T) -> String {
+    // Payloads only contain plain data with string keys, so serializing them cannot fail
+    serde_json::to_string(payload).expect("Serializing a payload cannot fail")
+}
 
-    lazy_static! {
-        static ref BLOCKING_HTTP_CLIENT: reqwest::blocking::Client = reqwest::blocking::Client::new();
+/// The query parameters for sending `request`: asking Discord to wait for the message to be created, and return it,
+/// if `wait` is set, and to respect the components, if the request has any
+fn message_query(request: &WebhookRequest<'_>, wait: bool) -> Vec<(&'static str, bool)> {
+    let mut query = Vec::new();
+    if wait {
+        query.push(("wait", true));
     }
 
+    if request.data.components.is_some() {
+        query.push(("with_components", true));
+    }
+
+    query
+}
+
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use crate::{WebhookRequest, WebhookUrl, IntoWebhookUrl, Message, Error, BlockingWebhookClient};
+    use super::{message_query, payload_json};
+    use serde::Serialize;
+    use reqwest::blocking::{Response, RequestBuilder};
+    use reqwest::blocking::multipart::{Form, Part};
+
     impl<'a> WebhookRequest<'a> {
-        /// Execute the current webhook request to the target URL
+        /// Attach the payload to the blocking request, see [WebhookRequest::attach_payload]
         ///
         /// # Errors
-        /// - When the request fails
-        pub fn execute_url_sync<S: AsRef<str>>(&self, url: S) -> Result<Response, Error> {
-            let mut req_builder = BLOCKING_HTTP_CLIENT.post(url.as_ref())
-                .json(&self.data);
+        /// - When an attachment's content type is not a valid media type
+        fn attach_payload_sync<T: Serialize>(&self, req_builder: RequestBuilder, payload: &T) -> Result<RequestBuilder, Error> {
+            let files = match self.files() {
+                Some(files) => files,
+                None => return Ok(req_builder.json(payload))
+            };
+
+            let mut form = Form::new()
+                .text("payload_json", payload_json(payload));
 
-            if let Some(f) = &self.file_name {
-                req_builder = req_builder.header("Content-Disposition", f);
+            for (idx, attachment) in files.iter().enumerate() {
+                let part = Part::bytes(attachment.data.clone())
+                    .file_name(attachment.upload_file_name())
+                    .mime_str(&attachment.mime_type())?;
+
+                form = form.part(format!("files[{}]", idx), part);
             }
 
-            req_builder.send()
+            Ok(req_builder.multipart(form))
+        }
+
+        /// Execute the current webhook request to the target URL
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        pub fn execute_url_sync<U: IntoWebhookUrl>(&self, url: U) -> Result<Response, Error> {
+            BlockingWebhookClient::shared().execute(self, url)
         }
 
         /// Execute the current webhook request to a target URL build from the given `id` and `token`
         ///
         /// # Errors
+        /// - When the request is invalid
         /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
         pub fn execute_sync<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Response, Error> {
-            let mut req_builder = BLOCKING_HTTP_CLIENT.post(format!("https://discord.com/api/webhooks/{}/{}", id.as_ref(), token.as_ref()))
-                .json(&self.data);
+            self.execute_url_sync(WebhookUrl::new(id, token)?)
+        }
+
+        /// Execute the current webhook request to the target URL, waiting for Discord to confirm the message has been created
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn execute_url_wait_sync<U: IntoWebhookUrl>(&self, url: U) -> Result<Message, Error> {
+            BlockingWebhookClient::shared().execute_wait(self, url)
+        }
 
-            if let Some(f) = &self.file_name {
-                req_builder = req_builder.header("Content-Disposition", f);
+        /// Execute the current webhook request to a target URL build from the given `id` and `token`,
+        /// waiting for Discord to confirm the message has been created
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn execute_wait_sync<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Message, Error> {
+            self.execute_url_wait_sync(WebhookUrl::new(id, token)?)
+        }
+
+        /// Execute the current webhook request to the target URL, splitting content and embeds exceeding the limits into multiple messages,
+        /// see [crate::WebhookClient::execute_split]
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request for any of the messages fails, in which case the remaining messages are not send
+        /// - When Discord responds with an unsuccessful status code
+        /// - When a returned message could not be deserialized
+        pub fn execute_url_split_sync<U: IntoWebhookUrl>(&self, url: U) -> Result<Vec<Message>, Error> {
+            BlockingWebhookClient::shared().execute_split(self, url)
+        }
+
+        /// Execute the current webhook request to a target URL build from the given `id` and `token`,
+        /// splitting content and embeds exceeding the limits into multiple messages, see [crate::WebhookClient::execute_split]
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request for any of the messages fails, in which case the remaining messages are not send
+        /// - When Discord responds with an unsuccessful status code
+        /// - When a returned message could not be deserialized
+        pub fn execute_split_sync<A: AsRef<str>, B: AsRef<str>>(&self, id: A, token: B) -> Result<Vec<Message>, Error> {
+            self.execute_url_split_sync(WebhookUrl::new(id, token)?)
+        }
+
+        /// Edit the message with the given id, previously send by the webhook at the target URL, see [crate::WebhookClient::edit]
+        ///
+        /// # Errors
+        /// - When the request is invalid, or sets a field which cannot be edited
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn edit_url_sync<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<Message, Error> {
+            BlockingWebhookClient::shared().edit(self, url, message_id)
+        }
+
+        /// Edit the message with the given id, previously send by the webhook with the given `id` and `token`, see [crate::WebhookClient::edit]
+        ///
+        /// # Errors
+        /// - When the request is invalid, or sets a field which cannot be edited
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn edit_sync<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(&self, id: A, token: B, message_id: M) -> Result<Message, Error> {
+            self.edit_url_sync(WebhookUrl::new(id, token)?, message_id)
+        }
+
+        /// Get the message with the given id, previously send by the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        /// - When the returned message could not be deserialized
+        pub fn get_message_url_sync<U: IntoWebhookUrl, M: AsRef<str>>(url: U, message_id: M) -> Result<Message, Error> {
+            BlockingWebhookClient::shared().get_message(url, message_id)
+        }
+
+        /// Get the message with the given id, previously send by the webhook with the given `id` and `token`
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        /// - When the returned message could not be deserialized
+        pub fn get_message_sync<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(id: A, token: B, message_id: M) -> Result<Message, Error> {
+            Self::get_message_url_sync(WebhookUrl::new(id, token)?, message_id)
+        }
+
+        /// Delete the message with the given id, previously send by the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        pub fn delete_message_url_sync<U: IntoWebhookUrl, M: AsRef<str>>(url: U, message_id: M) -> Result<(), Error> {
+            BlockingWebhookClient::shared().delete_message(url, message_id)
+        }
+
+        /// Delete the message with the given id, previously send by the webhook with the given `id` and `token`
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        pub fn delete_message_sync<A: AsRef<str>, B: AsRef<str>, M: AsRef<str>>(id: A, token: B, message_id: M) -> Result<(), Error> {
+            Self::delete_message_url_sync(WebhookUrl::new(id, token)?, message_id)
+        }
+    }
+
+    impl BlockingWebhookClient {
+        /// Execute the webhook request to the target URL
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        pub fn execute<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Response, Error> {
+            request.validate_single()?;
+
+            let url = url.into_webhook_url()?;
+            request.validate_thread(&url)?;
+            self.send(request, &url, false)
+        }
+
+        /// Execute the webhook request to the target URL, waiting for Discord to confirm the message has been created
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn execute_wait<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Message, Error> {
+            request.validate_single()?;
+
+            let url = url.into_webhook_url()?;
+            request.validate_thread(&url)?;
+            let response = self.send(request, &url, true)?;
+            Ok(response.json()?)
+        }
+
+        /// Execute the webhook request to the target URL, splitting content and embeds exceeding the limits into multiple messages,
+        /// see [crate::WebhookClient::execute_split]
+        ///
+        /// # Errors
+        /// - When the request is invalid
+        /// - When the request for any of the messages fails, in which case the remaining messages are not send
+        /// - When Discord responds with an unsuccessful status code
+        /// - When a returned message could not be deserialized
+        pub fn execute_split<U: IntoWebhookUrl>(&self, request: &WebhookRequest<'_>, url: U) -> Result<Vec<Message>, Error> {
+            request.validate()?;
+
+            let mut url = url.into_webhook_url()?;
+            request.validate_thread(&url)?;
+
+            let mut messages = Vec::new();
+            for request in request.split() {
+                let message: Message = self.send(&request, &url, true)?.json()?;
+
+                // The id of a forum post is the id of its channel
+                if request.data.thread_name.is_some() {
+                    url = url.with_thread_id(&message.channel_id)?;
+                }
+
+                messages.push(message);
             }
 
-            req_builder.send()
+            Ok(messages)
+        }
+
+        /// Send the request to the target URL, without validating it first, see [crate::WebhookClient::execute]
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        fn send(&self, request: &WebhookRequest<'_>, url: &WebhookUrl, wait: bool) -> Result<Response, Error> {
+            let request_url = url.request_url(&self.api_url());
+            self.send_request(url, false, |http| request.attach_payload_sync(http.post(&request_url).query(&message_query(request, wait)), &request.data))
+        }
+
+        /// Edit the message with the given id, previously send by the webhook at the target URL, see [crate::WebhookClient::edit]
+        ///
+        /// # Errors
+        /// - When the request is invalid, or sets a field which cannot be edited
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code
+        /// - When the returned message could not be deserialized
+        pub fn edit<U: IntoWebhookUrl, M: AsRef<str>>(&self, request: &WebhookRequest<'_>, url: U, message_id: M) -> Result<Message, Error> {
+            request.validate_edit()?;
+
+            let url = url.into_webhook_url()?;
+            let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+            let response = self.send_request(&url, true, |http| request.attach_payload_sync(http.patch(&message_url).query(&message_query(request, false)), &request.edit_payload()))?;
+            Ok(response.json()?)
+        }
+
+        /// Get the message with the given id, previously send by the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        /// - When the returned message could not be deserialized
+        pub fn get_message<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<Message, Error> {
+            let url = url.into_webhook_url()?;
+            let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+            let response = self.send_request(&url, true, |http| Ok(http.get(&message_url)))?;
+            Ok(response.json()?)
+        }
+
+        /// Delete the message with the given id, previously send by the webhook at the target URL
+        ///
+        /// # Errors
+        /// - When the request fails
+        /// - When Discord responds with an unsuccessful status code, e.g. when the message does not exist
+        pub fn delete_message<U: IntoWebhookUrl, M: AsRef<str>>(&self, url: U, message_id: M) -> Result<(), Error> {
+            let url = url.into_webhook_url()?;
+            let message_url = url.message_url(&self.api_url(), message_id.as_ref());
+            self.send_request(&url, true, |http| Ok(http.delete(&message_url)))?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+    use crate::stub::{stub_client, STUB_MESSAGE};
+
+    #[test]
+    fn attachments_send_as_multipart() {
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content("Build failed")
+                .build()
+            )
+            .add_attachment(AttachmentBuilder::new()
+                .set_file_name("build.log")
+                .set_data("Hello world!")
+                .build()
+            )
+            .build();
+
+        let request = req.attach_payload(reqwest::Client::new().post("https://discord.com/api/webhooks/1/token"), &req.data)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let content_type = request.headers().get(reqwest::header::CONTENT_TYPE).unwrap();
+        assert!(content_type.to_str().unwrap().starts_with("multipart/form-data; boundary="));
+    }
+
+    #[test]
+    fn embed_refers_to_attachment() {
+        let screenshot = AttachmentBuilder::new()
+            .set_file_name("screenshot.png")
+            .set_data(vec![0u8; 8])
+            .build();
+        let image = EmbedImageBuilder::new()
+            .set_attachment(&screenshot)
+            .build();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_embeds(vec![
+                    EmbedBuilder::new()
+                        .set_image(&image)
+                        .build()
+                ])
+                .build()
+            )
+            .add_attachment(screenshot.clone())
+            .build();
+
+        let payload = serde_json::to_value(&req.data).unwrap();
+        assert_eq!(payload["embeds"][0]["image"]["url"], "attachment://screenshot.png");
+    }
+
+    #[test]
+    fn duplicate_attachment_names() {
+        let attachment = |file_name: &str| AttachmentBuilder::new()
+            .set_file_name(file_name)
+            .set_data("Hello world!")
+            .build();
+
+        let err = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Build failed").build())
+            .add_attachment(attachment("build.log"))
+            .add_attachment(attachment("test.log"))
+            .add_attachment(attachment("build.log"))
+            .try_build()
+            .unwrap_err();
+        assert_eq!(err.violations.len(), 1);
+        assert_eq!(err.violations[0].path, "attachments[2].file_name");
+
+        // Both are uploaded as `build_log.txt`
+        let err = WebhookRequestBuilder::new()
+            .add_attachment(attachment("build log.txt"))
+            .add_attachment(attachment("build_log.txt"))
+            .try_build()
+            .unwrap_err();
+        assert_eq!(err.violations[0].path, "attachments[1].file_name");
+    }
+
+    #[test]
+    #[should_panic]
+    fn embed_refers_to_missing_attachment() {
+        let screenshot = AttachmentBuilder::new()
+            .set_file_name("screenshot.png")
+            .set_data(vec![0u8; 8])
+            .build();
+        let image = EmbedImageBuilder::new()
+            .set_attachment(&screenshot)
+            .build();
+
+        WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_embeds(vec![
+                    EmbedBuilder::new()
+                        .set_image(&image)
+                        .build()
+                ])
+                .build()
+            )
+            .build();
+    }
+
+    #[test]
+    fn split_request() {
+        let content = "a\n".repeat(1500);
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content(&content)
+                .set_username("CI")
+                .set_split(true)
+                .build()
+            )
+            .build();
+
+        let parts = req.split();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| part.data.username.as_deref() == Some("CI")));
+        assert!(parts.iter().all(|part| part.validate().is_ok()));
+        assert!(req.validate_single().is_err());
+    }
+
+    #[test]
+    fn split_request_with_embeds() {
+        let embed = EmbedBuilder::new()
+            .set_title("Embed")
+            .build();
+        let file = AttachmentBuilder::new()
+            .set_file_name("build.log")
+            .set_data("Hello world!")
+            .build();
+
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_embeds(vec![embed; 12])
+                .set_split(true)
+                .build()
+            )
+            .add_attachment(file)
+            .build();
+
+        let parts = req.split();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].data.embeds.as_ref().unwrap().len(), 10);
+        assert!(parts[0].data.attachments.is_none());
+        assert_eq!(parts[1].data.embeds.as_ref().unwrap().len(), 2);
+        assert!(parts[1].data.attachments.is_some());
+    }
+
+    #[test]
+    fn edit_payload_only_contains_changes() {
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content("Deploy succeeded")
+                .build()
+            )
+            .build();
+
+        let payload = serde_json::to_value(req.edit_payload()).unwrap();
+        assert_eq!(payload, serde_json::json!({"content": "Deploy succeeded"}));
+    }
+
+    #[test]
+    fn edit_payload_keeps_attachments() {
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content("Deploy succeeded")
+                .build()
+            )
+            .keep_attachment("123")
+            .add_attachment(AttachmentBuilder::new()
+                .set_file_name("build.log")
+                .set_data("Hello world!")
+                .build()
+            )
+            .build();
+
+        let payload = serde_json::to_value(req.edit_payload()).unwrap();
+        assert_eq!(payload["attachments"], serde_json::json!([{"id": "123"}, {"id": 0, "filename": "build.log"}]));
+
+        let removed = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Deploy succeeded").build())
+            .remove_attachments()
+            .build();
+        assert_eq!(serde_json::to_value(removed.edit_payload()).unwrap()["attachments"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn edit_only_removes_attachments() {
+        let req = WebhookRequestBuilder::new()
+            .remove_attachments()
+            .build();
+
+        assert!(req.validate_edit().is_ok());
+        assert_eq!(serde_json::to_value(req.edit_payload()).unwrap(), serde_json::json!({"attachments": []}));
+
+        // Executing a request without anything to show still fails
+        assert_eq!(req.validate_single().unwrap_err().violations[0].path, "");
+    }
+
+    #[test]
+    fn edit_only_clears_embeds() {
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_embeds(Vec::new()).build_edit())
+            .build();
+
+        assert!(req.validate_edit().is_ok());
+        assert_eq!(serde_json::to_value(req.edit_payload()).unwrap(), serde_json::json!({"embeds": []}));
+        assert!(req.validate().is_err());
+
+        assert!(WebhookBuilder::new().set_embeds(Vec::new()).try_build().is_err());
+        let content = "a".repeat(2001);
+        let err = WebhookBuilder::new().set_content(&content).try_build_edit().unwrap_err();
+        assert_eq!(err.violations[0].path, "content");
+    }
+
+    #[test]
+    fn edit_rejects_fixed_fields() {
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content("Deploy succeeded")
+                .set_username("Deploy bot")
+                .build()
+            )
+            .build();
+
+        let err = req.validate_edit().unwrap_err();
+        assert_eq!(err.violations.len(), 1);
+        assert_eq!(err.violations[0].path, "username");
+    }
+
+    #[test]
+    fn message_endpoints() {
+        let no_content = "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n";
+        let url = WebhookUrl::new("1", "token").unwrap();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .enable_io()
+            .build()
+            .unwrap();
+        let (builder, server) = stub_client(vec![STUB_MESSAGE, no_content]);
+        let client = builder.build();
+        assert_eq!(rt.block_on(client.get_message(url.clone(), "2")).unwrap().id, "2");
+        rt.block_on(client.delete_message(url.clone(), "2")).unwrap();
+
+        let (builder, blocking_server) = stub_client(vec![STUB_MESSAGE, no_content]);
+        let client = builder.build_blocking();
+        assert_eq!(client.get_message(url.clone(), "2").unwrap().id, "2");
+        client.delete_message(url, "2").unwrap();
+
+        for requests in [server.join().unwrap(), blocking_server.join().unwrap()].iter() {
+            assert_eq!(requests.len(), 2);
+            assert!(requests[0].starts_with("get /api/webhooks/1/token/messages/2 http/1.1"), "{}", requests[0]);
+            assert!(requests[1].starts_with("delete /api/webhooks/1/token/messages/2 http/1.1"), "{}", requests[1]);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn split_forum_post() {
+        let (builder, server) = stub_client(vec![STUB_MESSAGE, STUB_MESSAGE]);
+        let client = builder.build_blocking();
+
+        let content = "a".repeat(3000);
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new()
+                .set_content(&content)
+                .set_thread_name("Incident 42")
+                .set_split(true)
+                .build()
+            )
+            .build();
+
+        let messages = client.execute_split(&req, WebhookUrl::new("1", "token").unwrap()).unwrap();
+        assert_eq!(messages.len(), 2);
+
+        // The second message is send to the post created by the first, whose id is the channel id of the message
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /api/webhooks/1/token?wait=true "));
+        assert!(requests[0].contains("\"thread_name\":\"Incident 42\""));
+        assert!(requests[1].starts_with("post /api/webhooks/1/token?thread_id=3&wait=true "));
+        assert!(!requests[1].contains("thread_name"));
+    }
+
+    #[test]
+    fn components_query() {
+        let (builder, server) = stub_client(vec![STUB_MESSAGE]);
+        let client = builder.build_blocking();
+
+        let button = ButtonBuilder::new().set_label("View logs").set_url("https://ci.example.com/logs/42").build();
+        let req = WebhookRequestBuilder::new()
+            .set_data(WebhookBuilder::new().set_content("Deployed").set_components(vec![ActionRowBuilder::new().add_button(button).build()]).build())
+            .build();
+
+        client.execute_wait(&req, WebhookUrl::new("1", "token").unwrap()).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /api/webhooks/1/token?wait=true&with_components=true http/1.1"), "{}", requests[0]);
+        assert!(requests[0].contains("\"components\":[{\"type\":1"));
+    }
+}